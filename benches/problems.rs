@@ -0,0 +1,62 @@
+//! Per-day benchmarks driven by the problem registry
+//!
+//! Each day gets its own group (`day01`, `day02`, ...) containing `parse`, `part1` and `part2`
+//! cases, so a single day can be selected with e.g. `cargo bench -- day05`. Days without an input
+//! file are skipped.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aoc2023::{input, PROBLEMS};
+
+fn problems(c: &mut Criterion) {
+    for (idx, prob) in PROBLEMS.iter().enumerate() {
+        let p_num = idx + 1;
+
+        let raw = match std::fs::read(input::default_path(p_num)) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("{:02}: Skipping, failed to open input: {}", p_num, e);
+                continue;
+            }
+        };
+        let text = match input::normalize(&raw) {
+            Ok((text, _)) => text,
+            Err(e) => {
+                eprintln!("{:02}: Skipping, failed to read input: {}", p_num, e);
+                continue;
+            }
+        };
+        let parsed = match (prob.load_input)(&mut text.as_bytes()) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("{:02}: Skipping, failed to load input: {}", p_num, e);
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{:02}", p_num));
+        group.bench_function("parse", |b| {
+            b.iter(|| (prob.load_input)(&mut black_box(text.as_bytes())))
+        });
+        if let Some(p1) = prob.solve1 {
+            group.bench_function("part1", |b| b.iter(|| (p1)(Arc::clone(&parsed))));
+        }
+        if let Some(p2) = prob.solve2 {
+            group.bench_function("part2", |b| b.iter(|| (p2)(Arc::clone(&parsed))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+            .warm_up_time(Duration::from_secs(1))
+            .measurement_time(Duration::from_secs(3))
+            .noise_threshold(0.03);
+    targets = problems
+}
+criterion_main!(benches);
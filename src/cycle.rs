@@ -0,0 +1,140 @@
+//! Cycle detection for long-running deterministic simulations
+//!
+//! Simulations which are asked for the state after an enormous number of steps usually fall into
+//! a loop long before then. These helpers run a step function until a state repeats, then use the
+//! period to jump straight to any later step.
+
+use std::hash::{Hash, Hasher};
+
+use fnv::{FnvHashMap, FnvHasher};
+
+/// The history of a simulation up to the point where it starts repeating
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    /// Index of the first step which is part of the cycle
+    pub start: usize,
+
+    /// Number of steps in the cycle
+    pub length: usize,
+
+    /// Every distinct state, indexed by step
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Map an arbitrary step index onto the equivalent recorded step
+    pub fn index_of(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+
+    /// Get the state after a given number of steps
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.index_of(step)]
+    }
+
+    /// Consume the history, returning the state after a given number of steps
+    pub fn into_state_at(mut self, step: usize) -> S {
+        let idx = self.index_of(step);
+        self.states.swap_remove(idx)
+    }
+
+    /// Get every distinct state in order, starting with the initial one
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Hash a state for quick comparison against earlier ones
+fn state_hash<S: Hash>(state: &S) -> u64 {
+    let mut hasher = FnvHasher::default();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Record states produced by a step function until one repeats or `limit` steps have run
+///
+/// Returns the recorded states along with the cycle start, if one was found.
+fn run<S, F>(initial: S, mut step: F, limit: Option<usize>) -> (Vec<S>, Option<usize>)
+where S: Eq + Hash,
+      F: FnMut(&S) -> S,
+{
+    // states are only stored once; the index maps hashes to the steps which produced them
+    let mut index: FnvHashMap<u64, Vec<usize>> = FnvHashMap::default();
+    index.entry(state_hash(&initial)).or_default().push(0);
+    let mut states = vec![initial];
+
+    while limit.is_none_or(|l| states.len() <= l) {
+        let next = (step)(states.last().unwrap());
+        let hash = state_hash(&next);
+
+        let seen = index.entry(hash).or_default();
+        if let Some(&prev) = seen.iter().find(|&&i| states[i] == next) {
+            return (states, Some(prev));
+        }
+        seen.push(states.len());
+        states.push(next);
+    }
+
+    (states, None)
+}
+
+/// Run a simulation until it repeats a state
+///
+/// `step` computes the next state from the current one, and must be deterministic. This runs
+/// forever if the simulation never repeats.
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle<S>
+where S: Eq + Hash,
+      F: FnMut(&S) -> S,
+{
+    let (states, start) = run(initial, step, None);
+    let start = start.expect("Unbounded simulation stopped without finding a cycle");
+    Cycle { start, length: states.len() - start, states }
+}
+
+/// Get the state of a simulation after a given number of steps
+///
+/// If the simulation repeats before reaching that step, the result is found by skipping whole
+/// periods of the cycle rather than simulating them.
+pub fn state_at<S, F>(initial: S, step: F, n: usize) -> S
+where S: Eq + Hash,
+      F: FnMut(&S) -> S,
+{
+    let (mut states, start) = run(initial, step, Some(n));
+    match start {
+        Some(start) => Cycle { start, length: states.len() - start, states }.into_state_at(n),
+        None => states.swap_remove(n),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn integer_cycle() {
+        // 3 -> 10 -> 19 -> 34 -> 9 -> 0 -> 1 -> 2 -> 5 -> 26 -> 21 -> 32 -> 0 ...
+        let step = |x: &u64| (x * x + 1) % 41;
+        let cycle = find_cycle(3, step);
+
+        assert_eq!(cycle.start, 5);
+        assert_eq!(cycle.length, 7);
+        assert_eq!(*cycle.state_at(12), 0);
+        assert_eq!(*cycle.state_at(1_000_000_000), state_at(3, step, 1_000_000_000));
+        assert_eq!(state_at(3, step, 4), 9);
+    }
+
+    #[test]
+    fn grid_cycle() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 3*y);
+        let cycle = find_cycle(grid.clone(), |g| g.rotate_cw());
+
+        assert_eq!((cycle.start, cycle.length), (0, 4));
+        assert_eq!(cycle.state_at(1_000_000_001), &grid.rotate_cw());
+        assert_eq!(state_at(grid.clone(), |g| g.rotate_cw(), 1_000_000_002), grid.rotate_180());
+    }
+}
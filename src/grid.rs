@@ -1,10 +1,63 @@
 #![allow(dead_code)]
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+mod automaton;
+mod bfs;
+mod bits;
+mod grid3;
+mod hex;
+mod image;
+mod parse;
+mod rays;
+mod render;
+mod regions;
+mod runs;
+mod sparse;
+mod tiled;
+mod view;
+
+pub use automaton::Automaton;
+pub use bfs::DistanceField;
+pub use bits::BitGrid;
+pub use grid3::{Axis3, Coords3, Grid3, Neighborhood3};
+pub use hex::{Hex, HexDirection, HexMap, HexPoint, OffsetLayout};
+pub use image::{heat_color, FrameWriter, Rgb};
+pub use rays::{Line, Ray};
+pub use render::{Color, Glyph, Render};
+pub use regions::Components;
+pub use runs::{Axis, Run};
+pub use sparse::{SparseGrid, SparsePoint};
+pub use tiled::TiledPoint;
+pub use view::{GridView, ViewPoint};
+
+/// A dense two-dimensional grid of cells
+///
+/// Grids compare and hash by their shape and contents only. The [`Topology`] only affects
+/// navigation, so two grids holding the same cells are equal even if one of them wraps. Grids
+/// derived from another one (by [`map`](Self::map), [`filled_like`](Self::filled_like),
+/// [`padded`](Self::padded) or a transformation) keep its topology, while the `from_*` and
+/// [`filled`](Self::filled) constructors start out [`Bounded`](Topology::Bounded).
+#[derive(Clone, Debug)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
     height: usize,
+    topology: Topology,
+}
+
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.data == other.data
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Grid<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.data.hash(state);
+    }
 }
 
 impl<T> Grid<T> {
@@ -15,10 +68,11 @@ impl<T> Grid<T> {
     /// # Panics
     /// This will panic if `data.len()` is not divisible by `width`.
     pub fn from_data(data: Vec<T>, width: usize) -> Self {
-        assert!(data.len() % width == 0, "Data array is not evenly divisible into a grid");
+        assert!(data.len().is_multiple_of(width), "Data array is not evenly divisible into a grid");
         Self {
             height: data.len() / width,
             data, width,
+            topology: Topology::Bounded,
         }
     }
 
@@ -31,13 +85,13 @@ impl<T> Grid<T> {
             }
         }
 
-        Self { data, width, height }
+        Self { data, width, height, topology: Topology::Bounded }
     }
 
     /// Map the individual cell values through a function, returning a new grid
     pub fn map<U, F: Fn(&T) -> U>(self, func: F) -> Grid<U> {
         let data = self.data.iter().map(func).collect();
-        Grid { data, width: self.width, height: self.height }
+        Grid { data, width: self.width, height: self.height, topology: self.topology }
     }
 
     /// Get the width of the grid
@@ -50,6 +104,45 @@ impl<T> Grid<T> {
         self.height
     }
 
+    /// Get the addressing mode used when navigating between points
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Change the addressing mode used when navigating between points
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Return the grid with a different addressing mode
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Get the value at a signed position, wrapping around the edges of the grid
+    ///
+    /// This ignores the grid's topology, treating the plane as tiled with copies of the grid.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    #[inline]
+    pub fn get_wrapped(&self, pos: Pos) -> &T {
+        let (x, y) = self.wrap(pos);
+        &self.data[self.width*y + x]
+    }
+
+    /// Map a signed position onto the grid by wrapping around the edges
+    ///
+    /// # Panics
+    /// Panics if the grid is empty, since there is nothing to wrap onto.
+    #[inline]
+    fn wrap(&self, pos: Pos) -> (usize, usize) {
+        assert!(self.width > 0 && self.height > 0, "Attempted to wrap around an empty grid");
+        (pos.x.rem_euclid(self.width as isize) as usize,
+         pos.y.rem_euclid(self.height as isize) as usize)
+    }
+
     /// Get the value at given coordinates
     ///
     /// # Panics
@@ -63,13 +156,25 @@ impl<T> Grid<T> {
     }
 
     /// Try to get the value at given coordinates
+    ///
+    /// This accepts any [`GridIndex`], including signed positions which may be off the grid.
     #[inline]
-    pub fn try_get(&self, pos: (usize, usize)) -> Option<&T> {
-        if !(pos.0 < self.width && pos.1 < self.height) {
-            return None;
-        }
+    pub fn try_get<P: GridIndex>(&self, pos: P) -> Option<&T> {
+        let (x, y) = pos.to_coords(self.width, self.height)?;
+        Some(&self.data[self.width*y + x])
+    }
+
+    /// Try to get a mutable reference to the value at given coordinates
+    #[inline]
+    pub fn try_get_mut<P: GridIndex>(&mut self, pos: P) -> Option<&mut T> {
+        let (x, y) = pos.to_coords(self.width, self.height)?;
+        Some(&mut self.data[self.width*y + x])
+    }
 
-        Some(&self.data[self.width*pos.1 + pos.0])
+    /// Check whether a position lies inside the grid
+    #[inline]
+    pub fn contains<P: GridIndex>(&self, pos: P) -> bool {
+        pos.to_coords(self.width, self.height).is_some()
     }
 
     /// Get mutable reference to the value at given coordinates
@@ -104,9 +209,8 @@ impl<T> Grid<T> {
     }
 
     /// Iterate over each point on the grid in an unspecified order
-    pub fn points(&self) -> impl Iterator<Item=GridPoint<T>> {
-        (0..self.data.len()).into_iter()
-                            .map(|idx| GridPoint {
+    pub fn points(&self) -> impl Iterator<Item=GridPoint<'_, T>> {
+        (0..self.data.len()).map(|idx| GridPoint {
                                 index: idx,
                                 coords: (idx % self.width, idx / self.width),
                                 grid: self,
@@ -117,7 +221,7 @@ impl<T> Grid<T> {
     ///
     /// # Panics
     /// Panics if the given position is not inside the grid
-    pub fn point(&self, pos: (usize, usize)) -> GridPoint<T> {
+    pub fn point(&self, pos: (usize, usize)) -> GridPoint<'_, T> {
         assert!(pos.0 < self.width && pos.1 < self.height,
                 "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
         GridPoint {
@@ -127,6 +231,29 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Iterate over each point on the grid, with mutable access to its cell
+    ///
+    /// Points are yielded as `(coords, cell)` pairs in row-major order.
+    pub fn points_mut(&mut self) -> impl Iterator<Item=((usize, usize), &mut T)> {
+        let width = self.width;
+        self.data.iter_mut().enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    /// Get a mutable cursor positioned at a specific point on the grid
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid
+    pub fn cursor(&mut self, pos: (usize, usize)) -> GridCursor<'_, T> {
+        assert!(pos.0 < self.width && pos.1 < self.height,
+                "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
+        GridCursor {
+            coords: pos,
+            index: pos.0 + pos.1*self.width,
+            grid: self,
+        }
+    }
+
     /// Get an iterator over the cells in a given row
     ///
     /// # Panics
@@ -135,11 +262,11 @@ impl<T> Grid<T> {
     pub fn row_iter(
         &self,
         row: usize,
-    ) -> impl Iterator<Item=GridPoint<T>> + DoubleEndedIterator + ExactSizeIterator {
+    ) -> impl DoubleEndedIterator<Item=GridPoint<'_, T>> + ExactSizeIterator {
         assert!(row < self.height, "Attempted to access row outside the grid");
 
         let start_idx = row*self.width;
-        (start_idx..start_idx+self.width).into_iter().enumerate()
+        (start_idx..start_idx+self.width).enumerate()
             .map(move |(col, index)| GridPoint {
                 coords: (col, row),
                 index,
@@ -152,7 +279,7 @@ impl<T> Grid<T> {
     /// # Panics
     /// Panics if the given row is not inside the grid.
     #[inline]
-    pub fn row_iter_mut(&mut self, row: usize) -> impl Iterator<Item=&mut T> + DoubleEndedIterator + ExactSizeIterator {
+    pub fn row_iter_mut(&mut self, row: usize) -> impl DoubleEndedIterator<Item=&mut T> + ExactSizeIterator {
         assert!(row < self.height, "Attempted to access row outside the grid");
 
         let start_idx = row*self.width;
@@ -164,13 +291,10 @@ impl<T> Grid<T> {
     /// # Panics
     /// Panics if the given column is not inside the grid.
     #[inline]
-    pub fn col_iter(&self, col: usize) -> impl Iterator<Item=&T> + DoubleEndedIterator + ExactSizeIterator {
+    pub fn col_iter(&self, col: usize) -> impl DoubleEndedIterator<Item=&T> + ExactSizeIterator {
         assert!(col < self.width, "Attempted to access column outside the grid");
 
-        let start_idx = col;
-        let end_idx = self.data.len() - (self.width - col) + 1;
-        let content = &self.data[start_idx..end_idx];
-
+        let content = &self.data[self.col_span(col)];
         content.iter().step_by(self.width)
     }
 
@@ -179,37 +303,154 @@ impl<T> Grid<T> {
     /// # Panics
     /// Panics if the given column is not inside the grid.
     #[inline]
-    pub fn col_iter_mut(&mut self, col: usize) -> impl Iterator<Item=&mut T> + DoubleEndedIterator + ExactSizeIterator {
+    pub fn col_iter_mut(&mut self, col: usize) -> impl DoubleEndedIterator<Item=&mut T> + ExactSizeIterator {
         assert!(col < self.width, "Attempted to access column outside the grid");
 
-        let start_idx = col;
-        let end_idx = self.data.len() - (self.width - col) + 1;
-        let content = &mut self.data[start_idx..end_idx];
+        let span = self.col_span(col);
+        self.data[span].iter_mut().step_by(self.width)
+    }
 
-        content.iter_mut().step_by(self.width)
+    /// Get the range of flat indices spanning a column
+    ///
+    /// Stepping through the range by `width` yields exactly the column's cells, top to bottom.
+    #[inline]
+    fn col_span(&self, col: usize) -> std::ops::Range<usize> {
+        if self.height == 0 {
+            return 0..0;
+        }
+        col..self.data.len() - (self.width - col) + 1
     }
 
-    /// Display the grid to the console using a given rendering function
-    pub fn show_with<F: Fn(&T) -> char>(&self, func: F) {
-        eprintln!();
-        for row in self.data.chunks(self.width) {
-            for x in row {
-                eprint!("{}", (func)(x));
+    /// Mirror the grid left-to-right in place
+    pub fn flip_horizontal_in_place(&mut self) {
+        if self.width == 0 {
+            return;
+        }
+        for row in self.data.chunks_mut(self.width) {
+            row.reverse();
+        }
+    }
+
+    /// Mirror the grid top-to-bottom in place
+    pub fn flip_vertical_in_place(&mut self) {
+        for y in 0..self.height/2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y*self.width..(y+1)*self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    /// Rotate the grid by 180 degrees in place
+    pub fn rotate_180_in_place(&mut self) {
+        self.data.reverse();
+    }
+
+    /// Transpose a square grid in place, swapping its rows and columns
+    ///
+    /// # Panics
+    /// Panics if the grid isn't square.
+    pub fn transpose_in_place(&mut self) {
+        assert_eq!(self.width, self.height, "In-place transpose requires a square grid");
+        for y in 0..self.height {
+            for x in y+1..self.width {
+                self.data.swap(y*self.width + x, x*self.width + y);
+            }
+        }
+    }
+
+    /// Rotate a square grid 90 degrees clockwise in place
+    ///
+    /// # Panics
+    /// Panics if the grid isn't square.
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_horizontal_in_place();
+    }
+
+    /// Rotate a square grid 90 degrees counterclockwise in place
+    ///
+    /// # Panics
+    /// Panics if the grid isn't square.
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_vertical_in_place();
+    }
+
+    /// Apply an offset to a position, given both as a flat index and as coordinates
+    ///
+    /// Returns the new index and coordinates, or `None` if the result is outside the grid. On a
+    /// toroidal grid the result always exists.
+    #[inline]
+    fn offset_index(
+        &self,
+        index: usize,
+        coords: (usize, usize),
+        delta: (isize, isize),
+    ) -> Option<(usize, (usize, usize))> {
+        match self.topology {
+            Topology::Bounded => self.offset_index_bounded(index, coords, delta),
+            Topology::Torus => {
+                let (x, y) = self.wrap(Pos::from(coords) + Pos::from(delta));
+                Some((self.width*y + x, (x, y)))
+            }
+        }
+    }
+
+    /// Offset a point by a given amount, ignoring the grid's topology
+    ///
+    /// Returns the new index and coordinates, or `None` if the result is outside the grid.
+    #[inline]
+    fn offset_index_bounded(
+        &self,
+        mut index: usize,
+        (mut x, mut y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, (usize, usize))> {
+        // bounds check and offset X
+        if dx < 0 { // moving left
+            let dx = dx.unsigned_abs();
+            x = x.checked_sub(dx)?;
+            index -= dx;
+        } else { // moving right or not changing X
+            let dx = dx as usize;
+            x = x.checked_add(dx)?;
+            if x >= self.width {
+                return None;
+            }
+            index += dx;
+        }
+
+        // bounds check and offset Y
+        if dy < 0 { // moving left
+            let dy = (-dy) as usize;
+            y = y.checked_sub(dy)?;
+            index -= self.width * dy;
+        } else { // moving right or not changing X
+            let dy = dy as usize;
+            y = y.checked_add(dy)?;
+            if y >= self.height {
+                return None;
             }
-            eprintln!();
+            index += self.width * dy;
         }
+
+        Some((index, (x, y)))
+    }
+
+    /// Display the grid to the console using a given rendering function
+    pub fn show_with<F: Fn(&T) -> char>(&self, func: F) {
+        eprint!("\n{}", self.render_with(func));
     }
 }
 
 impl<T: Copy> Grid<T> {
     /// Create a new grid filled with a given value
     pub fn filled(width: usize, height: usize, data: T) -> Self {
-        Self { data: vec![data; width*height], width, height }
+        Self { data: vec![data; width*height], width, height, topology: Topology::Bounded }
     }
 
     /// Create a new grid, the same shape as another one, filled with a given value
     pub fn filled_like<U>(other: &Grid<U>, data: T) -> Self {
-        Self::filled(other.width, other.height, data)
+        Self::filled(other.width, other.height, data).with_topology(other.topology)
     }
 
     /// Pad the grid with a given value in every direction
@@ -225,7 +466,7 @@ impl<T: Copy> Grid<T> {
                 .copy_from_slice(&self.data[y*self.width..(y+1)*self.width]);
         }
 
-        Self { data: new_data, width: new_w, height: new_h }
+        Self { data: new_data, width: new_w, height: new_h, topology: self.topology }
     }
 
     /// Set every cell to a given value
@@ -234,28 +475,354 @@ impl<T: Copy> Grid<T> {
     }
 }
 
+impl<T: Clone> Grid<T> {
+    /// Return a transposed copy of the grid, swapping its rows and columns
+    pub fn transpose(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for col in 0..self.width {
+            data.extend(self.data[self.col_span(col)].iter().step_by(self.width).cloned());
+        }
+
+        Self { data, width: self.height, height: self.width, topology: self.topology }
+    }
+
+    /// Return a copy of the grid rotated 90 degrees clockwise
+    pub fn rotate_cw(&self) -> Self {
+        let mut out = self.transpose();
+        out.flip_horizontal_in_place();
+        out
+    }
+
+    /// Return a copy of the grid rotated 90 degrees counterclockwise
+    pub fn rotate_ccw(&self) -> Self {
+        let mut out = self.transpose();
+        out.flip_vertical_in_place();
+        out
+    }
+
+    /// Return a copy of the grid rotated by 180 degrees
+    pub fn rotate_180(&self) -> Self {
+        let mut out = self.clone();
+        out.rotate_180_in_place();
+        out
+    }
+
+    /// Return a copy of the grid mirrored left-to-right
+    pub fn flip_horizontal(&self) -> Self {
+        let mut out = self.clone();
+        out.flip_horizontal_in_place();
+        out
+    }
+
+    /// Return a copy of the grid mirrored top-to-bottom
+    pub fn flip_vertical(&self) -> Self {
+        let mut out = self.clone();
+        out.flip_vertical_in_place();
+        out
+    }
+
+    /// Iterate over all eight orientations of the grid under rotation and reflection
+    ///
+    /// The first four are the grid rotated clockwise by 0, 90, 180, and 270 degrees, and the last
+    /// four are the same rotations of the horizontally mirrored grid.
+    pub fn orientations(&self) -> impl Iterator<Item=Grid<T>> + '_ {
+        let mut current = self.clone();
+        (0..8).map(move |i| {
+            let out = current.clone();
+            current = if i == 3 { self.flip_horizontal() } else { current.rotate_cw() };
+            out
+        })
+    }
+}
+
 impl<T: PartialEq<T> + Eq> Grid<T> {
     /// Iterate over grid cells with a given value
-    pub fn find(&self, val: T) -> impl Iterator<Item=GridPoint<T>> {
+    pub fn find(&self, val: T) -> impl Iterator<Item=GridPoint<'_, T>> {
         use std::ops::Deref;
 
         self.points().filter(move |p| p.deref() == &val)
     }
 }
 
+impl<T, P: GridIndex> std::ops::Index<P> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: P) -> &T {
+        self.try_get(pos)
+            .unwrap_or_else(|| panic!("Attempted to access position {:?} outside grid", pos))
+    }
+}
+
+impl<T, P: GridIndex> std::ops::IndexMut<P> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        self.try_get_mut(pos)
+            .unwrap_or_else(|| panic!("Attempted to access position {:?} outside grid", pos))
+    }
+}
+
 impl std::fmt::Display for Grid<bool> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        writeln!(f)?;
-        for row in self.data.chunks(self.width) {
-            for b in row {
-                write!(f, "{}", if *b { '#' } else { ' ' })?;
-            }
-            writeln!(f)?;
+        write!(f, "\n{}", self.render())
+    }
+}
+
+/// Types which can be used to address a cell in a [`Grid`]
+pub trait GridIndex: Copy + std::fmt::Debug {
+    /// Convert to `(x, y)` coordinates, or `None` if the position is outside a grid of the given
+    /// size
+    fn to_coords(self, width: usize, height: usize) -> Option<(usize, usize)>;
+}
+
+impl GridIndex for (usize, usize) {
+    #[inline]
+    fn to_coords(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        (self.0 < width && self.1 < height).then_some(self)
+    }
+}
+
+impl GridIndex for Pos {
+    #[inline]
+    fn to_coords(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        self.to_unsigned()?.to_coords(width, height)
+    }
+}
+
+/// A position or displacement on an unbounded grid
+///
+/// Unlike plain `(usize, usize)` coordinates this can represent positions off the edge of a grid,
+/// and supports vector arithmetic.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Pos {
+    /// The origin, or zero displacement
+    pub const ZERO: Pos = Pos { x: 0, y: 0 };
+
+    /// Create a new position
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Get the Manhattan (taxicab) distance to another position
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Get the Chebyshev (king's move) distance to another position
+    pub fn chebyshev(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Get the position one step away in a given direction
+    pub fn step(self, dir: Direction) -> Self {
+        self + Pos::from(dir)
+    }
+
+    /// Convert to unsigned coordinates, if both components are non-negative
+    pub fn to_unsigned(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl From<(isize, isize)> for Pos {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Pos> for (isize, isize) {
+    fn from(p: Pos) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    /// # Panics
+    /// Panics if either coordinate doesn't fit in an `isize`.
+    fn from((x, y): (usize, usize)) -> Self {
+        Self {
+            x: x.try_into().expect("Coordinate out of range"),
+            y: y.try_into().expect("Coordinate out of range"),
         }
-        Ok(())
     }
 }
 
+impl From<Direction> for Pos {
+    fn from(dir: Direction) -> Self {
+        dir.offset().into()
+    }
+}
+
+impl std::ops::Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl std::ops::Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, other: Pos) -> Pos {
+        Pos { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl std::ops::Neg for Pos {
+    type Output = Pos;
+
+    fn neg(self) -> Pos {
+        Pos { x: -self.x, y: -self.y }
+    }
+}
+
+impl std::ops::Mul<isize> for Pos {
+    type Output = Pos;
+
+    fn mul(self, k: isize) -> Pos {
+        Pos { x: self.x * k, y: self.y * k }
+    }
+}
+
+impl std::ops::AddAssign for Pos {
+    fn add_assign(&mut self, other: Pos) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl std::ops::SubAssign for Pos {
+    fn sub_assign(&mut self, other: Pos) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+/// A direction of travel on a grid
+///
+/// Variants are ordered clockwise starting from [`Direction::Up`]. The Y axis points down, so
+/// `Up` corresponds to an offset of `(0, -1)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from `Up`
+    pub const ALL: [Direction; 8] = [
+        Direction::Up, Direction::UpRight, Direction::Right, Direction::DownRight,
+        Direction::Down, Direction::DownLeft, Direction::Left, Direction::UpLeft,
+    ];
+
+    /// The four cardinal directions, clockwise from `Up`
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up, Direction::Right, Direction::Down, Direction::Left,
+    ];
+
+    /// The four diagonal directions, clockwise from `UpRight`
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft,
+    ];
+
+    /// Rotate by a number of eighth-turns clockwise
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Get the `(dx, dy)` offset of a single step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up        => ( 0, -1),
+            Direction::UpRight   => ( 1, -1),
+            Direction::Right     => ( 1,  0),
+            Direction::DownRight => ( 1,  1),
+            Direction::Down      => ( 0,  1),
+            Direction::DownLeft  => (-1,  1),
+            Direction::Left      => (-1,  0),
+            Direction::UpLeft    => (-1, -1),
+        }
+    }
+
+    /// Find the direction corresponding to a single-step offset, if there is one
+    pub fn from_offset(offset: (isize, isize)) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.offset() == offset)
+    }
+
+    /// Check whether this is one of the four cardinal directions
+    pub fn is_cardinal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    /// Get the direction after a 90 degree turn to the left (counterclockwise)
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Get the direction after a 90 degree turn to the right (clockwise)
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Get the direction after a 45 degree turn to the left (counterclockwise)
+    pub fn turn_left_45(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// Get the direction after a 45 degree turn to the right (clockwise)
+    pub fn turn_right_45(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Get the opposite direction
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+}
+
+/// Set of moves considered adjacent when searching a grid
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// Up, down, left and right
+    Four,
+
+    /// All eight surrounding cells, including diagonals
+    Eight,
+}
+
+impl Neighborhood {
+    /// Get the directions making up this neighborhood
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Neighborhood::Four => &Direction::CARDINAL,
+            Neighborhood::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// How navigation behaves at the edges of a grid
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// Moving off an edge leaves the grid
+    #[default]
+    Bounded,
+
+    /// Moving off an edge wraps around to the opposite side
+    Torus,
+}
+
 /// A reference to a specific point on a grid
 pub struct GridPoint<'grid, T> {
     index: usize,
@@ -265,50 +832,16 @@ pub struct GridPoint<'grid, T> {
 
 impl<T> Clone for GridPoint<'_, T> {
     fn clone(&self) -> Self {
-        Self {
-            index: self.index,
-            coords: self.coords,
-            grid: self.grid,
-        }
+        *self
     }
 }
 impl<T> Copy for GridPoint<'_, T> {}
 
 impl<'g, T> GridPoint<'g, T> {
     /// Get the cell at a given offset relative to this one, if it exists
-    pub fn offset(&self, (dx, dy): (isize, isize)) -> Option<Self> {
-        let mut index = self.index;
-        let (mut x, mut y) = self.coords;
-
-        // bounds check and offset X
-        if dx < 0 { // moving left
-            let dx = dx.unsigned_abs();
-            x = x.checked_sub(dx)?;
-            index -= dx;
-        } else { // moving right or not changing X
-            let dx = dx as usize;
-            x = x.checked_add(dx)?;
-            if x >= self.grid.width {
-                return None;
-            }
-            index += dx;
-        }
-
-        // bounds check and offset Y
-        if dy < 0 { // moving left
-            let dy = (-dy) as usize;
-            y = y.checked_sub(dy)?;
-            index -= self.grid.width * dy;
-        } else { // moving right or not changing X
-            let dy = dy as usize;
-            y = y.checked_add(dy)?;
-            if y >= self.grid.height {
-                return None;
-            }
-            index += self.grid.width * dy;
-        }
-
-        Some(Self { grid: self.grid, index, coords: (x, y) })
+    pub fn offset(&self, delta: (isize, isize)) -> Option<Self> {
+        let (index, coords) = self.grid.offset_index(self.index, self.coords, delta)?;
+        Some(Self { grid: self.grid, index, coords })
     }
 
     /// Get the coordinates of this point
@@ -316,39 +849,29 @@ impl<'g, T> GridPoint<'g, T> {
         self.coords
     }
 
+    /// Get the coordinates of this point as a signed position
+    pub fn pos(&self) -> Pos {
+        self.coords.into()
+    }
+
     /// Get the cell to the left of this one, if it exists
     pub fn left(&self) -> Option<Self> {
-        if self.coords.0 > 0 {
-            Some(Self {
-                index: self.index - 1,
-                coords: (self.coords.0 - 1, self.coords.1),
-                grid: self.grid
-            })
-        } else {
-            None
-        }
+        self.step(Direction::Left)
     }
 
     /// Get the cell to the right of this one, if it exists
     pub fn right(&self) -> Option<Self> {
-        if self.coords.0 < (self.grid.width - 1) {
-            Some(Self {
-                index: self.index + 1,
-                coords: (self.coords.0 + 1, self.coords.1),
-                grid: self.grid
-            })
-        } else {
-            None
-        }
+        self.step(Direction::Right)
     }
 
     /// Walk to the left up to the edge of the grid
     ///
     /// This returns an iterator yielding all points between this one and the left edge of the
-    /// grid.
+    /// grid. On a torus the walk wraps around, visiting the rest of the row once.
     pub fn walk_left(&self) -> GridPointWalkRow<'g, T> {
         GridPointWalkRow {
             right: false,
+            start: self.index,
             point: *self,
         }
     }
@@ -356,46 +879,33 @@ impl<'g, T> GridPoint<'g, T> {
     /// Walk to the right up to the edge of the grid
     ///
     /// This returns an iterator yielding all points between this one and the right edge of the
-    /// grid.
+    /// grid. On a torus the walk wraps around, visiting the rest of the row once.
     pub fn walk_right(&self) -> GridPointWalkRow<'g, T> {
         GridPointWalkRow {
             right: true,
+            start: self.index,
             point: *self,
         }
     }
 
     /// Get the cell above this one, if it exists
     pub fn up(&self) -> Option<Self> {
-        if self.coords.1 > 0 {
-            Some(Self {
-                index: self.index - self.grid.width,
-                coords: (self.coords.0, self.coords.1 - 1),
-                grid: self.grid
-            })
-        } else {
-            None
-        }
+        self.step(Direction::Up)
     }
 
-    /// Get the cell to the right of this one, if it exists
+    /// Get the cell below this one, if it exists
     pub fn down(&self) -> Option<Self> {
-        if self.coords.1 < (self.grid.height - 1) {
-            Some(Self {
-                index: self.index + self.grid.width,
-                coords: (self.coords.0, self.coords.1 + 1),
-                grid: self.grid
-            })
-        } else {
-            None
-        }
+        self.step(Direction::Down)
     }
 
     /// Walk upwards to the edge of the grid
     ///
     /// This returns an iterator yielding all points between this one and the top edge of the grid.
+    /// On a torus the walk wraps around, visiting the rest of the column once.
     pub fn walk_up(&self) -> GridPointWalkColumn<'g, T> {
         GridPointWalkColumn {
             down: false,
+            start: self.index,
             point: *self,
         }
     }
@@ -403,19 +913,82 @@ impl<'g, T> GridPoint<'g, T> {
     /// Walk downwards to the edge of the grid
     ///
     /// This returns an iterator yielding all points between this one and the bottom edge of the
-    /// grid.
+    /// grid. On a torus the walk wraps around, visiting the rest of the column once.
     pub fn walk_down(&self) -> GridPointWalkColumn<'g, T> {
         GridPointWalkColumn {
             down: true,
+            start: self.index,
             point: *self,
         }
     }
 
     /// Iterate over neighboring cells
-    pub fn neighbors<'a>(&'a self) -> impl Iterator<Item=GridPoint<'g, T>> + 'a {
-        [(-1,-1), (0, -1), (1, -1),
-         (-1,0),           (1, 0),
-         (-1,1),  (0, 1),  (1, 1)].into_iter().filter_map(|delta| self.offset(delta))
+    ///
+    /// Each neighbor is yielded once, and never the cell itself, even on a torus too small for
+    /// every direction to reach a different cell.
+    pub fn neighbors(&self) -> impl Iterator<Item=GridPoint<'g, T>> {
+        let point = *self;
+        let deltas = [(-1,-1), (0, -1), (1, -1),
+                      (-1,0),           (1, 0),
+                      (-1,1),  (0, 1),  (1, 1)];
+        deltas.into_iter().filter_map(move |delta| point.offset(delta)).filter(self.unseen())
+    }
+
+    /// Get the adjacent cell in a given direction, if it exists
+    pub fn step(&self, dir: Direction) -> Option<Self> {
+        self.offset(dir.offset())
+    }
+
+    /// Iterate over the cardinal (up, down, left, and right) neighbors of this cell
+    ///
+    /// As with [`neighbors`](Self::neighbors), each neighbor is yielded once.
+    pub fn neighbors4(&self) -> impl Iterator<Item=GridPoint<'g, T>> {
+        let point = *self;
+        Direction::CARDINAL.into_iter().filter_map(move |dir| point.step(dir)).filter(self.unseen())
+    }
+
+    /// Build a filter rejecting cells that coincide with this one or with a cell it accepted
+    /// earlier
+    ///
+    /// This can only happen on a torus less than three cells wide or tall.
+    fn unseen(&self) -> impl FnMut(&GridPoint<'g, T>) -> bool {
+        let wraps = self.grid.topology == Topology::Torus;
+        let mut seen = [self.index; 9];
+        let mut len = 1;
+        move |p| {
+            if !wraps {
+                return true;
+            }
+            if seen[..len].contains(&p.index) {
+                return false;
+            }
+            seen[len] = p.index;
+            len += 1;
+            true
+        }
+    }
+
+    /// Iterate over all neighbors of this cell, along with the direction taken to reach each one
+    ///
+    /// Neighbors are yielded clockwise starting from [`Direction::Up`]. As with
+    /// [`neighbors`](Self::neighbors), each neighbor is yielded once, along with the first
+    /// direction reaching it.
+    pub fn neighbors_dir(&self) -> impl Iterator<Item=(Direction, GridPoint<'g, T>)> {
+        let point = *self;
+        let mut unseen = self.unseen();
+        Direction::ALL.into_iter()
+            .filter_map(move |dir| Some((dir, point.step(dir)?)))
+            .filter(move |(_, p)| (unseen)(p))
+    }
+
+    /// Iterate over the cardinal neighbors of this cell, along with the direction taken to reach
+    /// each one
+    pub fn neighbors4_dir(&self) -> impl Iterator<Item=(Direction, GridPoint<'g, T>)> {
+        let point = *self;
+        let mut unseen = self.unseen();
+        Direction::CARDINAL.into_iter()
+            .filter_map(move |dir| Some((dir, point.step(dir)?)))
+            .filter(move |(_, p)| (unseen)(p))
     }
 }
 
@@ -429,11 +1002,13 @@ impl<'g, T> std::ops::Deref for GridPoint<'g, T> {
 
 pub struct GridPointWalkRow<'g, T> {
     right: bool,
+    start: usize,
     point: GridPoint<'g, T>,
 }
 
 pub struct GridPointWalkColumn<'g, T> {
     down: bool,
+    start: usize,
     point: GridPoint<'g, T>,
 }
 
@@ -441,12 +1016,10 @@ impl<'g, T> Iterator for GridPointWalkRow<'g, T> {
     type Item = GridPoint<'g, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.right {
-            self.point = self.point.right()?;
-        } else {
-            self.point = self.point.left()?;
-        }
-        Some(self.point)
+        let dx = if self.right { 1 } else { -1 };
+        let next = self.point.offset((dx, 0)).filter(|p| p.index != self.start)?;
+        self.point = next;
+        Some(next)
     }
 }
 
@@ -454,12 +1027,116 @@ impl<'g, T> Iterator for GridPointWalkColumn<'g, T> {
     type Item = GridPoint<'g, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.down {
-            self.point = self.point.down()?;
-        } else {
-            self.point = self.point.up()?;
+        let dy = if self.down { 1 } else { -1 };
+        let next = self.point.offset((0, dy)).filter(|p| p.index != self.start)?;
+        self.point = next;
+        Some(next)
+    }
+}
+
+/// A mutable cursor over a grid
+///
+/// This offers the same navigation as [`GridPoint`], but moves in place and can modify the cell it
+/// currently points at. Navigation methods return `false` and leave the cursor where it was if the
+/// move would leave the grid.
+pub struct GridCursor<'grid, T> {
+    index: usize,
+    coords: (usize, usize),
+    grid: &'grid mut Grid<T>,
+}
+
+impl<'g, T> GridCursor<'g, T> {
+    /// Get the coordinates of the current cell
+    pub fn coords(&self) -> (usize, usize) {
+        self.coords
+    }
+
+    /// Move by a given offset, if the destination is inside the grid
+    pub fn offset(&mut self, delta: (isize, isize)) -> bool {
+        match self.grid.offset_index(self.index, self.coords, delta) {
+            Some((index, coords)) => {
+                self.index = index;
+                self.coords = coords;
+                true
+            }
+            None => false,
         }
-        Some(self.point)
+    }
+
+    /// Move one cell in a given direction, if the destination is inside the grid
+    pub fn step(&mut self, dir: Direction) -> bool {
+        self.offset(dir.offset())
+    }
+
+    /// Move one cell to the left, if possible
+    pub fn left(&mut self) -> bool {
+        self.step(Direction::Left)
+    }
+
+    /// Move one cell to the right, if possible
+    pub fn right(&mut self) -> bool {
+        self.step(Direction::Right)
+    }
+
+    /// Move one cell up, if possible
+    pub fn up(&mut self) -> bool {
+        self.step(Direction::Up)
+    }
+
+    /// Move one cell down, if possible
+    pub fn down(&mut self) -> bool {
+        self.step(Direction::Down)
+    }
+
+    /// Move to an absolute position on the grid
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    pub fn jump(&mut self, pos: (usize, usize)) {
+        assert!(pos.0 < self.grid.width && pos.1 < self.grid.height,
+                "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
+        self.coords = pos;
+        self.index = pos.0 + pos.1*self.grid.width;
+    }
+
+    /// Read the cell at a given offset from the cursor without moving, if it exists
+    pub fn peek(&self, delta: (isize, isize)) -> Option<&T> {
+        let (index, _) = self.grid.offset_index(self.index, self.coords, delta)?;
+        Some(&self.grid.data[index])
+    }
+
+    /// Replace the value of the current cell
+    pub fn set(&mut self, val: T) {
+        self.grid.data[self.index] = val;
+    }
+
+    /// Get an immutable view of the current point, for use with [`GridPoint`] navigation
+    pub fn point(&self) -> GridPoint<'_, T> {
+        GridPoint { index: self.index, coords: self.coords, grid: self.grid }
+    }
+
+    /// Iterate over the cells neighboring the cursor, as with [`GridPoint::neighbors`]
+    pub fn neighbors(&self) -> impl Iterator<Item=GridPoint<'_, T>> {
+        self.point().neighbors()
+    }
+
+    /// Iterate over the cardinal neighbors of the cursor, as with [`GridPoint::neighbors4`]
+    pub fn neighbors4(&self) -> impl Iterator<Item=GridPoint<'_, T>> {
+        self.point().neighbors4()
+    }
+}
+
+impl<T> std::ops::Deref for GridCursor<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.grid.data[self.index]
+    }
+}
+
+impl<T> std::ops::DerefMut for GridCursor<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.grid.data[self.index]
     }
 }
 
@@ -496,4 +1173,196 @@ mod test {
         assert_eq!(grid.col_iter(2).rev().cloned().collect::<Vec<_>>(), vec![5, 4, 3, 2]);
         assert_eq!(grid.col_iter(3).rev().cloned().collect::<Vec<_>>(), vec![6, 5, 4, 3]);
     }
+
+    #[test]
+    fn cursor_navigation() {
+        let mut grid = Grid::filled(3, 2, 0);
+
+        let mut cursor = grid.cursor((0, 0));
+        assert!(!cursor.left());
+        assert!(!cursor.up());
+        *cursor = 1;
+        assert!(cursor.right());
+        assert!(cursor.step(Direction::DownRight));
+        cursor.set(2);
+        assert_eq!(cursor.coords(), (2, 1));
+        assert!(!cursor.offset((1, 0)));
+        assert_eq!(cursor.coords(), (2, 1));
+        assert_eq!(cursor.peek((-2, -1)), Some(&1));
+        assert_eq!(cursor.point().up().map(|p| *p), Some(0));
+        assert_eq!(cursor.neighbors().map(|p| p.coords()).collect::<Vec<_>>(),
+                   vec![(1, 0), (2, 0), (1, 1)]);
+        assert_eq!(cursor.neighbors4().map(|p| p.coords()).collect::<Vec<_>>(),
+                   vec![(2, 0), (1, 1)]);
+
+        for ((x, y), cell) in grid.points_mut() {
+            *cell += 10*x + 100*y;
+        }
+        assert_eq!(grid.cells().cloned().collect::<Vec<_>>(), vec![1, 10, 20, 100, 110, 122]);
+    }
+
+    #[test]
+    fn torus_navigation() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10*y).with_topology(Topology::Torus);
+
+        let corner = grid.point((0, 0));
+        assert_eq!(corner.left().map(|p| p.coords()), Some((2, 0)));
+        assert_eq!(corner.up().map(|p| p.coords()), Some((0, 1)));
+        assert_eq!(corner.offset((-4, 5)).map(|p| *p), Some(12));
+        assert_eq!(corner.neighbors4().count(), 3);
+        assert_eq!(corner.neighbors().count(), 5);
+        assert_eq!(corner.walk_right().map(|p| *p).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(corner.walk_left().map(|p| *p).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(grid.point((1, 1)).walk_up().map(|p| *p).collect::<Vec<_>>(), vec![1]);
+
+        let small = Grid::from_fn(2, 2, |x, y| x + 2*y).with_topology(Topology::Torus);
+        let mut cells = small.point((0, 0)).neighbors().map(|p| *p).collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells, vec![1, 2, 3]);
+
+        let dirs = small.point((0, 0)).neighbors4_dir().map(|(d, p)| (d, p.coords()))
+                  .collect::<Vec<_>>();
+        assert_eq!(dirs, vec![(Direction::Up, (0, 1)), (Direction::Right, (1, 0))]);
+        assert_eq!(small.point((1, 1)).neighbors_dir().count(), 3);
+
+        let column = Grid::from_fn(1, 4, |_, y| y).with_topology(Topology::Torus);
+        let dirs = column.point((0, 0)).neighbors_dir().map(|(d, p)| (d, *p)).collect::<Vec<_>>();
+        assert_eq!(dirs, vec![(Direction::Up, 3), (Direction::DownRight, 1)]);
+        assert_eq!(column.point((0, 2)).neighbors4_dir().count(), 2);
+
+        let single = Grid::filled(1, 1, 0).with_topology(Topology::Torus);
+        assert_eq!(single, Grid::filled(1, 1, 0));
+        assert_eq!(Grid::filled_like(&single, 'x').topology(), Topology::Torus);
+        assert_eq!(single.point((0, 0)).neighbors4().count(), 0);
+        assert_eq!(single.point((0, 0)).neighbors().count(), 0);
+        assert_eq!(single.point((0, 0)).neighbors_dir().count(), 0);
+
+        let field = grid.bfs([(0, 0)], Neighborhood::Four, |_, _| true);
+        assert_eq!(field.distance((2, 1)), Some(2));
+
+        let mut grid = grid;
+        let mut cursor = grid.cursor((2, 1));
+        assert!(cursor.step(Direction::DownRight));
+        assert_eq!(cursor.coords(), (0, 0));
+    }
+
+    #[test]
+    fn signed_positions() {
+        let mut grid = Grid::from_fn(3, 3, |x, y| x + 3*y);
+
+        assert_eq!(grid[(1, 2)], 7);
+        assert_eq!(grid[Pos::new(2, 0)], 2);
+        assert_eq!(grid.try_get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.try_get(Pos::new(0, 3)), None);
+        assert_eq!(grid.try_get((3, 0)), None);
+        assert!(!grid.contains(Pos::new(isize::MIN, 0)));
+
+        grid[Pos::new(1, 1)] = 40;
+        grid[(0, 0)] += 1;
+        assert_eq!(grid.point((1, 1)).pos(), Pos::new(1, 1));
+        assert_eq!(*grid.get((1, 1)), 40);
+        assert_eq!(*grid.get((0, 0)), 1);
+
+        let a = Pos::new(1, -2);
+        let b = Pos::new(-3, 4);
+        assert_eq!(a + b, Pos::new(-2, 2));
+        assert_eq!(a - b, Pos::new(4, -6));
+        assert_eq!(-a * 2, Pos::new(-2, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.step(Direction::UpLeft), Pos::new(0, -3));
+        assert_eq!(a.to_unsigned(), None);
+    }
+
+    #[test]
+    fn transforms() {
+        // 0 1 2
+        // 3 4 5
+        let grid = Grid::from_fn(3, 2, |x, y| x + 3*y);
+        let cells = |g: &Grid<usize>| (g.width(), g.cells().cloned().collect::<Vec<_>>());
+
+        assert_eq!(cells(&grid.transpose()), (2, vec![0, 3, 1, 4, 2, 5]));
+        assert_eq!(cells(&grid.rotate_cw()), (2, vec![3, 0, 4, 1, 5, 2]));
+        assert_eq!(cells(&grid.rotate_ccw()), (2, vec![2, 5, 1, 4, 0, 3]));
+        assert_eq!(cells(&grid.rotate_180()), (3, vec![5, 4, 3, 2, 1, 0]));
+        assert_eq!(cells(&grid.flip_horizontal()), (3, vec![2, 1, 0, 5, 4, 3]));
+        assert_eq!(cells(&grid.flip_vertical()), (3, vec![3, 4, 5, 0, 1, 2]));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        let orientations = grid.orientations().collect::<Vec<_>>();
+        assert_eq!(orientations.len(), 8);
+        for (i, a) in orientations.iter().enumerate() {
+            assert!(orientations[i+1..].iter().all(|b| a != b));
+        }
+        assert!(orientations.contains(&grid.flip_vertical()));
+    }
+
+    #[test]
+    fn transforms_in_place() {
+        let grid = Grid::from_fn(4, 4, |x, y| x + 4*y);
+
+        let mut g = grid.clone();
+        g.transpose_in_place();
+        assert_eq!(g, grid.transpose());
+
+        let mut g = grid.clone();
+        g.rotate_cw_in_place();
+        assert_eq!(g, grid.rotate_cw());
+
+        let mut g = grid.clone();
+        g.rotate_ccw_in_place();
+        assert_eq!(g, grid.rotate_ccw());
+
+        let mut g = grid.clone();
+        g.flip_vertical_in_place();
+        assert_eq!(g, grid.flip_vertical());
+
+        let transposed = grid.transpose();
+        for col in 0..4 {
+            assert!(grid.col_iter(col).cloned().eq(transposed.row_iter(col).map(|p| *p)));
+        }
+    }
+
+    #[test]
+    fn transforms_empty() {
+        let grid = Grid::from_fn(3, 0, |x, y| x + y);
+        assert_eq!(grid.col_iter(1).count(), 0);
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (0, 3));
+        assert_eq!(grid.rotate_cw().height(), 3);
+    }
+
+    #[test]
+    fn direction_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(dir.turn_left_45().turn_left_45(), dir.turn_left());
+            assert_eq!(Direction::from_offset(dir.offset()), Some(dir));
+
+            let (dx, dy) = dir.offset();
+            assert_eq!(dir.opposite().offset(), (-dx, -dy));
+        }
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::UpLeft.turn_left(), Direction::DownLeft);
+        assert_eq!(Direction::from_offset((2, 0)), None);
+    }
+
+    #[test]
+    fn cardinal_neighbors() {
+        let grid = Grid::from_fn(3, 3, |x, y| (x, y));
+
+        let corner = grid.point((0, 0)).neighbors4_dir()
+                    .map(|(d, p)| (d, *p)).collect::<Vec<_>>();
+        assert_eq!(corner, vec![(Direction::Right, (1, 0)), (Direction::Down, (0, 1))]);
+
+        let center = grid.point((1, 1));
+        assert_eq!(center.neighbors4().count(), 4);
+        assert_eq!(center.neighbors_dir().count(), 8);
+        assert_eq!(center.step(Direction::UpLeft).map(|p| *p), Some((0, 0)));
+        assert!(grid.point((2, 2)).step(Direction::DownRight).is_none());
+    }
 }
@@ -0,0 +1,140 @@
+//! Double-buffered cellular automaton stepping
+
+use rayon::prelude::*;
+
+use super::{Grid, GridPoint};
+
+/// A grid which is repeatedly updated by computing each cell's next value from the current
+/// generation
+///
+/// Two buffers are kept and swapped after every step, so no allocation happens once the
+/// automaton has been created.
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    front: Grid<T>,
+    back: Grid<T>,
+    generation: usize,
+}
+
+impl<T: Clone> Automaton<T> {
+    /// Create an automaton starting from a given grid
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { back: grid.clone(), front: grid, generation: 0 }
+    }
+}
+
+impl<T> Automaton<T> {
+    /// Get the current generation
+    pub fn grid(&self) -> &Grid<T> {
+        &self.front
+    }
+
+    /// Consume the automaton, returning the current generation
+    pub fn into_grid(self) -> Grid<T> {
+        self.front
+    }
+
+    /// Get the number of steps taken so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Make the back buffer current and advance the generation count
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.front, &mut self.back);
+        self.generation += 1;
+    }
+}
+
+impl<T: PartialEq> Automaton<T> {
+    /// Advance one generation
+    ///
+    /// `rule` is called with each point of the current generation and returns that cell's next
+    /// value; neighbors are available through the point's navigation methods. Returns whether any
+    /// cell changed.
+    pub fn step<F: Fn(GridPoint<'_, T>) -> T>(&mut self, rule: F) -> bool {
+        let front = &self.front;
+        let mut changed = false;
+        for (index, next) in self.back.data.iter_mut().enumerate() {
+            let coords = (index % front.width, index / front.width);
+            *next = (rule)(GridPoint { index, coords, grid: front });
+            changed |= *next != front.data[index];
+        }
+
+        self.swap();
+        changed
+    }
+
+    /// Step until a generation leaves every cell unchanged
+    ///
+    /// Returns the number of steps taken, including the final one which changed nothing.
+    pub fn run_until_stable<F: Fn(GridPoint<'_, T>) -> T>(&mut self, rule: F) -> usize {
+        let start = self.generation;
+        while self.step(&rule) {}
+        self.generation - start
+    }
+}
+
+impl<T: PartialEq + Send + Sync> Automaton<T> {
+    /// Advance one generation, computing rows in parallel
+    ///
+    /// This behaves exactly like [`Automaton::step`], but splits the grid's rows across the rayon
+    /// thread pool.
+    pub fn par_step<F: Fn(GridPoint<'_, T>) -> T + Sync>(&mut self, rule: F) -> bool {
+        let front = &self.front;
+        let width = front.width;
+        let changed = self.back.data.par_chunks_mut(width.max(1))
+            .enumerate()
+            .map(|(y, row)| {
+                let mut changed = false;
+                for (x, next) in row.iter_mut().enumerate() {
+                    let index = y*width + x;
+                    *next = (rule)(GridPoint { index, coords: (x, y), grid: front });
+                    changed |= *next != front.data[index];
+                }
+                changed
+            })
+            .reduce(|| false, |a, b| a || b);
+
+        self.swap();
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn life(p: GridPoint<'_, bool>) -> bool {
+        let live = p.neighbors().filter(|n| **n).count();
+        live == 3 || (*p && live == 2)
+    }
+
+    #[test]
+    fn blinker() {
+        let start = Grid::from_fn(5, 5, |x, y| y == 2 && (1..4).contains(&x));
+
+        let mut seq = Automaton::new(start.clone());
+        let mut par = Automaton::new(start.clone());
+        assert!(seq.step(life));
+        assert!(par.par_step(life));
+        assert_eq!(seq.grid(), par.grid());
+        assert_eq!(*seq.grid(), start.transpose());
+
+        assert!(seq.step(life));
+        assert_eq!(seq.generation(), 2);
+        assert_eq!(seq.into_grid(), start);
+    }
+
+    #[test]
+    fn stabilize() {
+        // spread the maximum value until it covers the grid
+        let start = Grid::from_fn(4, 3, |x, y| (x, y) == (0, 0));
+        let mut auto = Automaton::new(start);
+        let steps = auto.run_until_stable(|p| *p || p.neighbors4().any(|n| *n));
+
+        assert_eq!(steps, 6);
+        assert!(auto.grid().cells().all(|c| *c));
+        assert!(!auto.par_step(|p| *p));
+    }
+}
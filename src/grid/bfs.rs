@@ -0,0 +1,211 @@
+//! Breadth-first distance fields
+
+use std::collections::VecDeque;
+
+use super::{Direction, Grid, GridPoint, Neighborhood};
+
+/// Result of a breadth-first search over a grid
+///
+/// Holds the distance from the nearest start point to every reachable cell, along with enough
+/// information to reconstruct a shortest path to any of them.
+#[derive(Clone, Debug)]
+pub struct DistanceField {
+    distances: Grid<Option<u32>>,
+    parents: Grid<Option<(usize, usize)>>,
+}
+
+impl<T> Grid<T> {
+    /// Compute distances from a set of start points, deciding passability per move
+    ///
+    /// `can_move(from, to)` is called for each candidate step and should return whether it's
+    /// allowed. Start points are always reachable at distance zero.
+    ///
+    /// # Panics
+    /// Panics if any start point is not inside the grid.
+    pub fn bfs<I, F>(&self, starts: I, neighborhood: Neighborhood, mut can_move: F) -> DistanceField
+    where I: IntoIterator<Item=(usize, usize)>,
+          F: FnMut(GridPoint<'_, T>, GridPoint<'_, T>) -> bool,
+    {
+        let mut distances = Grid::filled_like(self, None);
+        let mut parents = Grid::filled_like(self, None);
+
+        breadth_first(
+            starts.into_iter().map(|start| self.point(start)),
+            neighborhood,
+            u32::MAX,
+            |cur, dir| cur.step(dir),
+            |from, to, dist| {
+                if distances.get(to.coords()).is_some() {
+                    return false;
+                }
+                if let Some(from) = from {
+                    if !(can_move)(from, to) {
+                        return false;
+                    }
+                    parents.set(to.coords(), Some(from.coords()));
+                }
+                distances.set(to.coords(), Some(dist));
+                true
+            },
+        );
+
+        DistanceField { distances, parents }
+    }
+
+    /// Compute distances from a set of start points through cells matching a predicate
+    ///
+    /// Cells for which `passable` returns `false` are never entered, though start points are
+    /// always reachable.
+    ///
+    /// # Panics
+    /// Panics if any start point is not inside the grid.
+    pub fn bfs_cells<I, F>(&self, starts: I, neighborhood: Neighborhood, passable: F) -> DistanceField
+    where I: IntoIterator<Item=(usize, usize)>,
+          F: Fn(&T) -> bool,
+    {
+        self.bfs(starts, neighborhood, |_, to| (passable)(&to))
+    }
+}
+
+/// Run the queue of a breadth-first search over any kind of point
+///
+/// `step(point, dir)` finds the neighbor in a direction, if there is one. `reach(from, to, dist)`
+/// is called for each start point (with `from` set to `None`) and each candidate step, and
+/// should record `to` at distance `dist` and return `true` if it hasn't been reached before and
+/// the step is allowed. Points at `max_distance` are not expanded.
+pub(super) fn breadth_first<P, S, R>(
+    starts: impl IntoIterator<Item=P>,
+    neighborhood: Neighborhood,
+    max_distance: u32,
+    mut step: S,
+    mut reach: R,
+)
+where P: Copy,
+      S: FnMut(P, Direction) -> Option<P>,
+      R: FnMut(Option<P>, P, u32) -> bool,
+{
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if (reach)(None, start, 0) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((cur, dist)) = queue.pop_front() {
+        if dist == max_distance {
+            continue;
+        }
+
+        for dir in neighborhood.directions() {
+            let Some(next) = (step)(cur, *dir) else { continue; };
+            if (reach)(Some(cur), next, dist + 1) {
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+}
+
+impl DistanceField {
+    /// Get the distance to a cell, or `None` if it is unreachable
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    pub fn distance(&self, pos: (usize, usize)) -> Option<u32> {
+        *self.distances.get(pos)
+    }
+
+    /// Get the full grid of distances
+    pub fn distances(&self) -> &Grid<Option<u32>> {
+        &self.distances
+    }
+
+    /// Consume the search result, returning the grid of distances
+    pub fn into_distances(self) -> Grid<Option<u32>> {
+        self.distances
+    }
+
+    /// Reconstruct a shortest path to a cell
+    ///
+    /// The path runs from one of the start points to `target` inclusive, or is `None` if the
+    /// target is unreachable.
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    pub fn path_to(&self, target: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        self.distance(target)?;
+
+        let mut path = vec![target];
+        let mut cur = target;
+        while let Some(prev) = *self.parents.get(cur) {
+            path.push(prev);
+            cur = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn maze() -> Grid<bool> {
+        // open cells are `true`
+        const MAZE: &[&str] = &[
+            "..#....",
+            ".##.##.",
+            "....#..",
+            "###.#.#",
+            "..#....",
+        ];
+        let data = MAZE.iter().flat_map(|r| r.chars()).map(|c| c == '.').collect();
+        Grid::from_data(data, MAZE[0].len())
+    }
+
+    #[test]
+    fn single_source() {
+        let grid = maze();
+        let field = grid.bfs_cells([(0, 0)], Neighborhood::Four, |open| *open);
+
+        assert_eq!(field.distance((0, 0)), Some(0));
+        assert_eq!(field.distance((3, 0)), Some(7));
+        assert_eq!(field.distance((6, 4)), Some(10));
+        assert_eq!(field.distance((0, 4)), None);
+        assert_eq!(field.distance((2, 0)), None);
+
+        let path = field.path_to((6, 4)).unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(6, 4)));
+        for pair in path.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+            assert!(*grid.get(b));
+        }
+        assert_eq!(field.path_to((0, 4)), None);
+    }
+
+    #[test]
+    fn multi_source_diagonal() {
+        let grid = maze();
+        let field = grid.bfs_cells([(0, 0), (6, 0)], Neighborhood::Eight, |open| *open);
+
+        assert_eq!(field.distance((6, 0)), Some(0));
+        assert_eq!(field.distance((5, 2)), Some(2));
+        assert_eq!(field.distance((2, 0)), None);
+        assert_eq!(field.path_to((6, 0)), Some(vec![(6, 0)]));
+    }
+
+    #[test]
+    fn move_predicate() {
+        // only allow moving to cells with a value at most one higher
+        let grid = Grid::from_data(vec![0, 1, 5, 2,
+                                        1, 2, 3, 4], 4);
+        let field = grid.bfs([(0, 0)], Neighborhood::Four, |from, to| *to <= *from + 1);
+
+        assert_eq!(field.distance((2, 0)), None);
+        assert_eq!(field.distance((3, 1)), Some(4));
+        assert_eq!(field.distance((3, 0)), Some(5));
+        assert_eq!(field.distances().cells().filter(|d| d.is_some()).count(), 7);
+    }
+}
@@ -0,0 +1,399 @@
+//! Bit-packed boolean grids
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::{Grid, Neighborhood};
+
+/// A grid of booleans stored as packed 64-bit words
+///
+/// Each row starts on a word boundary, with cell `x` held in bit `x % 64` of word `x / 64`. Bits
+/// past the end of a row are always zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl BitGrid {
+    /// Create a grid with every cell unset
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+        Self { words: vec![0; stride*height], width, height, stride }
+    }
+
+    /// Create a grid with the same dimensions as another, with every cell unset
+    pub fn new_like(other: &BitGrid) -> Self {
+        Self::new(other.width, other.height)
+    }
+
+    /// Get the width of the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Mask of valid bits in the last word of each row
+    #[inline]
+    fn tail_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => !0,
+            n => (1 << n) - 1,
+        }
+    }
+
+    /// Clear any bits past the end of each row
+    fn mask_tails(&mut self) {
+        if self.stride == 0 {
+            return;
+        }
+        let mask = self.tail_mask();
+        for row in self.words.chunks_mut(self.stride) {
+            *row.last_mut().unwrap() &= mask;
+        }
+    }
+
+    /// Get the word index and bit mask for a given cell
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    fn locate(&self, pos: (usize, usize)) -> (usize, u64) {
+        assert!(pos.0 < self.width && pos.1 < self.height,
+                "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
+        (pos.1*self.stride + pos.0/64, 1 << (pos.0 % 64))
+    }
+
+    /// Get the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> bool {
+        let (word, bit) = self.locate(pos);
+        self.words[word] & bit != 0
+    }
+
+    /// Set the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    pub fn set(&mut self, pos: (usize, usize), val: bool) {
+        let (word, bit) = self.locate(pos);
+        if val {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// Count the number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Check whether any cell is set
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|w| *w != 0)
+    }
+
+    /// Iterate over the coordinates of every set cell in row-major order
+    pub fn ones(&self) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(idx, word)| {
+            let (y, base) = (idx / self.stride, (idx % self.stride) * 64);
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some((base + bit, y))
+            })
+        })
+    }
+
+    /// Move every cell `n` columns to the right, or to the left if `n` is negative
+    ///
+    /// Cells moved off the edge are discarded, and vacated cells are unset.
+    pub fn shift_x(&self, n: isize) -> Self {
+        let mut out = Self::new_like(self);
+        let words = n.unsigned_abs() / 64;
+        let bits = (n.unsigned_abs() % 64) as u32;
+        if words >= self.stride {
+            return out;
+        }
+
+        for (src, dst) in self.words.chunks(self.stride).zip(out.words.chunks_mut(self.stride)) {
+            if n >= 0 {
+                // towards higher bits, carrying from the word below
+                for i in words..self.stride {
+                    let lo = src[i - words];
+                    let carry = if bits > 0 && i > words { src[i - words - 1] >> (64 - bits) } else { 0 };
+                    dst[i] = (lo << bits) | carry;
+                }
+            } else {
+                // towards lower bits, carrying from the word above
+                for i in 0..self.stride - words {
+                    let hi = src[i + words];
+                    let carry = match src.get(i + words + 1) {
+                        Some(next) if bits > 0 => next << (64 - bits),
+                        _ => 0,
+                    };
+                    dst[i] = (hi >> bits) | carry;
+                }
+            }
+        }
+
+        out.mask_tails();
+        out
+    }
+
+    /// Move every cell `n` rows down, or up if `n` is negative
+    ///
+    /// Cells moved off the edge are discarded, and vacated cells are unset.
+    pub fn shift_y(&self, n: isize) -> Self {
+        let mut out = Self::new_like(self);
+        let rows = n.unsigned_abs().min(self.height);
+        let moved = (self.height - rows) * self.stride;
+        if n >= 0 {
+            out.words[rows*self.stride..].copy_from_slice(&self.words[..moved]);
+        } else {
+            out.words[..moved].copy_from_slice(&self.words[rows*self.stride..]);
+        }
+        out
+    }
+
+    /// Move every cell by a given offset
+    pub fn shifted(&self, (dx, dy): (isize, isize)) -> Self {
+        self.shift_x(dx).shift_y(dy)
+    }
+
+    /// Count the set neighbors of every cell, as bit planes
+    ///
+    /// Plane `i` of the result has a cell set if bit `i` of that cell's neighbor count is set.
+    /// Counts are computed for all cells at once using bitwise ripple-carry addition.
+    pub fn neighbor_planes(&self, neighborhood: Neighborhood) -> [BitGrid; 4] {
+        let mut planes = std::array::from_fn(|_| Self::new_like(self));
+
+        for dir in neighborhood.directions() {
+            // a cell's neighbor in direction `dir` is moved onto it by shifting the opposite way
+            let (dx, dy) = dir.offset();
+            let shifted = self.shifted((-dx, -dy));
+            for (idx, mut carry) in shifted.words.into_iter().enumerate() {
+                for plane in planes.iter_mut() {
+                    let sum = plane.words[idx] ^ carry;
+                    carry &= plane.words[idx];
+                    plane.words[idx] = sum;
+                    if carry == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        planes
+    }
+
+    /// Count the set neighbors of every cell
+    pub fn neighbor_counts(&self, neighborhood: Neighborhood) -> Grid<u8> {
+        let planes = self.neighbor_planes(neighborhood);
+        Grid::from_fn(self.width, self.height, |x, y| {
+            planes.iter().enumerate().map(|(i, p)| (p.get((x, y)) as u8) << i).sum()
+        })
+    }
+
+    /// Find every cell whose number of set neighbors is one of the given counts
+    pub fn with_neighbor_count(&self, neighborhood: Neighborhood, counts: &[u8]) -> Self {
+        let planes = self.neighbor_planes(neighborhood);
+        let mut out = Self::new_like(self);
+        for &count in counts {
+            let mut matched = !&Self::new_like(self);
+            for (i, plane) in planes.iter().enumerate() {
+                if count & (1 << i) != 0 {
+                    matched &= plane;
+                } else {
+                    matched &= &!plane;
+                }
+            }
+            out |= &matched;
+        }
+        out
+    }
+
+    /// Apply a binary operation word by word
+    ///
+    /// # Panics
+    /// Panics if the grids have different dimensions.
+    fn zip_assign<F: Fn(&mut u64, u64)>(&mut self, other: &BitGrid, op: F) {
+        assert!(self.width == other.width && self.height == other.height,
+                "Attempted to combine grids of different sizes");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            (op)(a, *b);
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_assign(rhs, |a, b| *a &= b);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_assign(rhs, |a, b| *a |= b);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_assign(rhs, |a, b| *a ^= b);
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: &BitGrid) -> BitGrid {
+        let mut out = self.clone();
+        out &= rhs;
+        out
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: &BitGrid) -> BitGrid {
+        let mut out = self.clone();
+        out |= rhs;
+        out
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: &BitGrid) -> BitGrid {
+        let mut out = self.clone();
+        out ^= rhs;
+        out
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut out = self.clone();
+        out.words.iter_mut().for_each(|w| *w = !*w);
+        out.mask_tails();
+        out
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut out = Self::new(grid.width, grid.height);
+        for (idx, _) in grid.data.iter().enumerate().filter(|(_, b)| **b) {
+            out.set((idx % grid.width, idx / grid.width), true);
+        }
+        out
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(grid: &BitGrid) -> Self {
+        Grid::from_fn(grid.width, grid.height, |x, y| grid.get((x, y)))
+    }
+}
+
+impl std::fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get((x, y)) { '#' } else { ' ' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A pseudo-random grid wide enough to span several words per row
+    fn noise(width: usize, height: usize) -> Grid<bool> {
+        let mut rng = crate::util::Rng::new(7);
+        let data = (0..width*height).map(|_| rng.below(3) == 0).collect();
+        Grid::from_data(data, width)
+    }
+
+    #[test]
+    fn conversion_and_ops() {
+        let dense = noise(150, 4);
+        let bits = BitGrid::from(&dense);
+        assert_eq!(Grid::from(&bits), dense);
+        assert_eq!(bits.count_ones(), dense.cells().filter(|b| **b).count());
+        assert_eq!(bits.to_string(), dense.to_string());
+        assert!(bits.ones().all(|p| *dense.get(p)));
+
+        let inverse = !&bits;
+        assert_eq!(inverse.count_ones(), 150*4 - bits.count_ones());
+        assert!(!(&bits & &inverse).any());
+        assert_eq!((&bits | &inverse).count_ones(), 150*4);
+        assert_eq!(&bits ^ &bits, BitGrid::new(150, 4));
+    }
+
+    #[test]
+    fn shifts() {
+        let dense = noise(130, 3);
+        let bits = BitGrid::from(&dense);
+
+        for n in [0, 1, 5, 63, 64, 65, 129, 200] {
+            for n in [n, -n] {
+                let expected = Grid::from_fn(130, 3, |x, y| {
+                    let src = x as isize - n;
+                    (0..130).contains(&src) && *dense.get((src as usize, y))
+                });
+                assert_eq!(Grid::from(&bits.shift_x(n)), expected, "shift_x({})", n);
+            }
+        }
+
+        let down = bits.shift_y(1);
+        assert_eq!(down.ones().filter(|p| p.1 == 0).count(), 0);
+
+        let mut clipped = bits.clone();
+        (0..130).for_each(|x| clipped.set((x, 2), false));
+        assert_eq!(down.shift_y(-1), clipped);
+    }
+
+    #[test]
+    fn neighbor_counts() {
+        let dense = noise(70, 5);
+        let bits = BitGrid::from(&dense);
+
+        let counts = bits.neighbor_counts(Neighborhood::Eight);
+        for p in dense.points() {
+            assert_eq!(*counts.get(p.coords()) as usize, p.neighbors().filter(|n| **n).count());
+        }
+
+        // one generation of Conway's game of life
+        let next = &(&bits & &bits.with_neighbor_count(Neighborhood::Eight, &[2]))
+                 | &bits.with_neighbor_count(Neighborhood::Eight, &[3]);
+        for p in dense.points() {
+            let live = p.neighbors().filter(|n| **n).count();
+            assert_eq!(next.get(p.coords()), live == 3 || (*p && live == 2));
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Three-dimensional grids
+
+use std::collections::VecDeque;
+
+use super::Grid;
+
+/// Coordinates of a cell in a [`Grid3`]
+pub type Coords3 = (usize, usize, usize);
+
+/// A dense three-dimensional grid
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+    depth: usize,
+}
+
+/// Set of moves considered adjacent in a three-dimensional grid
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Neighborhood3 {
+    /// Cells sharing a face
+    Six,
+
+    /// Cells sharing a face, edge or corner
+    TwentySix,
+}
+
+impl Neighborhood3 {
+    /// Get the offsets making up this neighborhood
+    pub fn offsets(self) -> &'static [(isize, isize, isize)] {
+        const FACES: [(isize, isize, isize); 6] = [
+            (-1, 0, 0), (1, 0, 0), (0, -1, 0), (0, 1, 0), (0, 0, -1), (0, 0, 1),
+        ];
+        const ALL: [(isize, isize, isize); 26] = {
+            let mut out = [(0, 0, 0); 26];
+            let mut i = 0;
+            let mut n = 0;
+            while n < 27 {
+                if n != 13 {
+                    out[i] = (n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+                    i += 1;
+                }
+                n += 1;
+            }
+            out
+        };
+
+        match self {
+            Neighborhood3::Six => &FACES,
+            Neighborhood3::TwentySix => &ALL,
+        }
+    }
+}
+
+/// An axis of a three-dimensional grid
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Axis3 {
+    /// Along the width
+    X,
+
+    /// Along the height
+    Y,
+
+    /// Along the depth
+    Z,
+}
+
+impl<T> Grid3<T> {
+    /// Construct a grid from a flat data vector, ordered by `x`, then `y`, then `z`
+    ///
+    /// The depth will be set automatically based on the number of elements and the width and
+    /// height.
+    ///
+    /// # Panics
+    /// This will panic if `data.len()` is not divisible by `width * height`.
+    pub fn from_data(data: Vec<T>, width: usize, height: usize) -> Self {
+        assert!(data.len().is_multiple_of(width*height),
+                "Data array is not evenly divisible into a grid");
+        Self {
+            depth: data.len() / (width*height),
+            data, width, height,
+        }
+    }
+
+    /// Construct a grid by calling a function with each coordinate
+    pub fn from_fn<F: Fn(usize, usize, usize) -> T>(
+        width: usize,
+        height: usize,
+        depth: usize,
+        func: F,
+    ) -> Self {
+        let mut data = Vec::with_capacity(width*height*depth);
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    data.push((func)(x, y, z));
+                }
+            }
+        }
+
+        Self { data, width, height, depth }
+    }
+
+    /// Get the width (extent along X) of the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height (extent along Y) of the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the depth (extent along Z) of the grid
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the index of a cell in the data array
+    #[inline]
+    fn index(&self, (x, y, z): Coords3) -> usize {
+        x + self.width*(y + self.height*z)
+    }
+
+    /// Check whether a position lies inside the grid
+    #[inline]
+    pub fn contains(&self, (x, y, z): Coords3) -> bool {
+        x < self.width && y < self.height && z < self.depth
+    }
+
+    /// Get the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    pub fn get(&self, pos: Coords3) -> &T {
+        assert!(self.contains(pos),
+                "Attempted to access position ({}, {}, {}) outside grid", pos.0, pos.1, pos.2);
+        &self.data[self.index(pos)]
+    }
+
+    /// Try to get the value at given coordinates
+    #[inline]
+    pub fn try_get(&self, pos: Coords3) -> Option<&T> {
+        self.contains(pos).then(|| &self.data[self.index(pos)])
+    }
+
+    /// Get mutable reference to the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    pub fn get_mut(&mut self, pos: Coords3) -> &mut T {
+        assert!(self.contains(pos),
+                "Attempted to access position ({}, {}, {}) outside grid", pos.0, pos.1, pos.2);
+        let idx = self.index(pos);
+        &mut self.data[idx]
+    }
+
+    /// Set the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    #[inline]
+    pub fn set(&mut self, pos: Coords3, val: T) {
+        *self.get_mut(pos) = val;
+    }
+
+    /// Iterate over all cells in unspecified order
+    pub fn cells(&self) -> impl Iterator<Item=&T> {
+        self.data.iter()
+    }
+
+    /// Iterate over the coordinates of every cell along with its value
+    pub fn points(&self) -> impl Iterator<Item=(Coords3, &T)> {
+        let (w, h) = (self.width, self.height);
+        self.data.iter().enumerate().map(move |(i, v)| ((i % w, i / w % h, i / (w*h)), v))
+    }
+
+    /// Get the cell at a given offset from a position, if it exists
+    pub fn offset(&self, (x, y, z): Coords3, (dx, dy, dz): (isize, isize, isize)) -> Option<Coords3> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?, z.checked_add_signed(dz)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Iterate over the neighbors of a cell which lie inside the grid
+    pub fn neighbors(
+        &self,
+        pos: Coords3,
+        neighborhood: Neighborhood3,
+    ) -> impl Iterator<Item=Coords3> + '_ {
+        neighborhood.offsets().iter().filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// Find all cells in the region containing `start`
+    ///
+    /// Two adjacent cells are in the same region if `same_region(from, to)` returns true. Cells
+    /// are returned in breadth-first order starting with `start`.
+    ///
+    /// # Panics
+    /// Panics if `start` is not inside the grid.
+    pub fn flood_fill<F>(
+        &self,
+        start: Coords3,
+        neighborhood: Neighborhood3,
+        mut same_region: F,
+    ) -> Vec<Coords3>
+    where F: FnMut(Coords3, Coords3) -> bool,
+    {
+        assert!(self.contains(start),
+                "Attempted to access position ({}, {}, {}) outside grid", start.0, start.1, start.2);
+
+        let mut visited = vec![false; self.data.len()];
+        visited[self.index(start)] = true;
+
+        let mut out = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(cur) = queue.pop_front() {
+            for next in self.neighbors(cur, neighborhood) {
+                let idx = self.index(next);
+                if visited[idx] || !(same_region)(cur, next) {
+                    continue;
+                }
+
+                visited[idx] = true;
+                out.push(next);
+                queue.push_back(next);
+            }
+        }
+
+        out
+    }
+
+    /// Count the faces of cells matching a predicate which border a non-matching cell or the
+    /// edge of the grid
+    pub fn surface_area<F: Fn(&T) -> bool>(&self, pred: F) -> usize {
+        self.points()
+            .filter(|(_, v)| (pred)(v))
+            .map(|(pos, _)| {
+                Neighborhood3::Six.offsets().iter()
+                    .filter(|d| self.offset(pos, **d).is_none_or(|n| !(pred)(self.get(n))))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl<T: Copy> Grid3<T> {
+    /// Create a new grid filled with a given value
+    pub fn filled(width: usize, height: usize, depth: usize, data: T) -> Self {
+        Self { data: vec![data; width*height*depth], width, height, depth }
+    }
+
+    /// Pad the grid with a given value in every direction
+    ///
+    /// This will return a new grid that adds `n` copies of `val` to each side.
+    pub fn padded(&self, val: T, n: usize) -> Self {
+        Self::from_fn(self.width + 2*n, self.height + 2*n, self.depth + 2*n, |x, y, z| {
+            match (x.checked_sub(n), y.checked_sub(n), z.checked_sub(n)) {
+                (Some(x), Some(y), Some(z)) => self.try_get((x, y, z)).copied().unwrap_or(val),
+                _ => val,
+            }
+        })
+    }
+}
+
+impl<T: Clone> Grid3<T> {
+    /// Extract a two-dimensional slice perpendicular to an axis
+    ///
+    /// Slicing along Z yields an X-Y grid, along Y an X-Z grid, and along X a Y-Z grid.
+    ///
+    /// # Panics
+    /// Panics if `index` is outside the grid along the given axis.
+    pub fn slice(&self, axis: Axis3, index: usize) -> Grid<T> {
+        match axis {
+            Axis3::X => Grid::from_fn(self.height, self.depth, |y, z| self.get((index, y, z)).clone()),
+            Axis3::Y => Grid::from_fn(self.width, self.depth, |x, z| self.get((x, index, z)).clone()),
+            Axis3::Z => Grid::from_fn(self.width, self.height, |x, y| self.get((x, y, index)).clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn access_and_slicing() {
+        let grid = Grid3::from_fn(2, 3, 4, |x, y, z| x + 10*y + 100*z);
+        assert_eq!(grid, Grid3::from_data(grid.cells().copied().collect(), 2, 3));
+        assert_eq!(*grid.get((1, 2, 3)), 321);
+        assert_eq!(grid.try_get((2, 0, 0)), None);
+
+        assert_eq!(grid.slice(Axis3::Z, 1), Grid::from_fn(2, 3, |x, y| x + 10*y + 100));
+        assert_eq!(grid.slice(Axis3::Y, 2), Grid::from_fn(2, 4, |x, z| x + 20 + 100*z));
+        assert_eq!(grid.slice(Axis3::X, 0), Grid::from_fn(3, 4, |y, z| 10*y + 100*z));
+
+        assert_eq!(grid.neighbors((0, 0, 0), Neighborhood3::Six).count(), 3);
+        assert_eq!(grid.neighbors((0, 1, 1), Neighborhood3::TwentySix).count(), 17);
+        assert_eq!(Neighborhood3::TwentySix.offsets().iter().filter(|d| **d == (0, 0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn hollow_cube_surface() {
+        // a 3x3x3 block with a single air pocket in the middle
+        let solid = Grid3::from_fn(3, 3, 3, |x, y, z| (x, y, z) != (1, 1, 1));
+        assert_eq!(solid.surface_area(|s| *s), 54 + 6);
+
+        // flood the outside air to find only the exterior surface
+        let padded = solid.padded(false, 1);
+        let outside = padded.flood_fill((0, 0, 0), Neighborhood3::Six, |_, to| !*padded.get(to));
+        assert_eq!(outside.len(), 5*5*5 - 27);
+
+        let exterior = outside.iter()
+            .flat_map(|p| padded.neighbors(*p, Neighborhood3::Six))
+            .filter(|n| *padded.get(*n))
+            .count();
+        assert_eq!(exterior, 54);
+    }
+}
@@ -0,0 +1,406 @@
+//! Hexagonal grids using axial coordinates
+//!
+//! Hexes are "pointy-top", so they form horizontal rows and each row is offset by half a cell from
+//! its neighbors. This matches the usual ASCII rendering where alternate lines are indented.
+
+use anyhow::Result;
+
+use super::parse::{check_width, grid_lines};
+
+/// A position on an unbounded hexagonal grid, in axial coordinates
+///
+/// The third cube coordinate `s` is implied by `q + r + s == 0`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// One of the six directions between adjacent hexes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+/// Which rows are shifted right by half a cell when hexes are stored in rows
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetLayout {
+    /// Odd rows are shifted right
+    OddR,
+
+    /// Even rows are shifted right
+    EvenR,
+}
+
+impl HexDirection {
+    /// All directions, counter-clockwise starting from [`HexDirection::East`]
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East, HexDirection::NorthEast, HexDirection::NorthWest,
+        HexDirection::West, HexDirection::SouthWest, HexDirection::SouthEast,
+    ];
+
+    /// Get the axial `(q, r)` offset of one step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::SouthEast => (0, 1),
+        }
+    }
+
+    /// Get the direction rotated 60 degrees counter-clockwise
+    pub fn rotate_ccw(self) -> Self {
+        Self::ALL[(self as usize + 1) % 6]
+    }
+
+    /// Get the direction rotated 60 degrees clockwise
+    pub fn rotate_cw(self) -> Self {
+        Self::ALL[(self as usize + 5) % 6]
+    }
+
+    /// Get the opposite direction
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+}
+
+impl Hex {
+    /// The origin
+    pub const ZERO: Hex = Hex { q: 0, r: 0 };
+
+    /// Create a hex from axial coordinates
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Get the implied third cube coordinate
+    pub fn s(self) -> isize {
+        -self.q - self.r
+    }
+
+    /// Get the cube `(q, r, s)` coordinates of this hex
+    pub fn cube(self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Get the number of steps needed to reach another hex
+    pub fn distance(self, other: Hex) -> usize {
+        let d = self - other;
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+
+    /// Get the adjacent hex in a given direction
+    pub fn step(self, dir: HexDirection) -> Self {
+        let (dq, dr) = dir.offset();
+        Self { q: self.q + dq, r: self.r + dr }
+    }
+
+    /// Iterate over all six adjacent hexes, counter-clockwise starting from the east
+    pub fn neighbors(self) -> impl Iterator<Item=Hex> {
+        HexDirection::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// Iterate over the hexes exactly `radius` steps away, going counter-clockwise
+    ///
+    /// A radius of zero yields only this hex.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item=Hex> {
+        let mut cur = self + Hex::from(HexDirection::SouthWest) * radius as isize;
+        let len = if radius == 0 { 1 } else { 6*radius };
+        (0..len).map(move |i| {
+            let out = cur;
+            if let Some(side) = i.checked_div(radius) {
+                cur = cur.step(HexDirection::ALL[side]);
+            }
+            out
+        })
+    }
+
+    /// Iterate over every hex within `radius` steps, ring by ring outwards from this one
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item=Hex> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+
+    /// Convert offset-row coordinates to a hex
+    pub fn from_offset((col, row): (isize, isize), layout: OffsetLayout) -> Self {
+        let shift = match layout {
+            OffsetLayout::OddR => (row - (row & 1)) / 2,
+            OffsetLayout::EvenR => (row + (row & 1)) / 2,
+        };
+        Self { q: col - shift, r: row }
+    }
+
+    /// Convert this hex to offset-row coordinates
+    pub fn to_offset(self, layout: OffsetLayout) -> (isize, isize) {
+        let shift = match layout {
+            OffsetLayout::OddR => (self.r - (self.r & 1)) / 2,
+            OffsetLayout::EvenR => (self.r + (self.r & 1)) / 2,
+        };
+        (self.q + shift, self.r)
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(dir: HexDirection) -> Self {
+        let (q, r) = dir.offset();
+        Self { q, r }
+    }
+}
+
+impl std::ops::Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex { q: self.q + other.q, r: self.r + other.r }
+    }
+}
+
+impl std::ops::Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex { q: self.q - other.q, r: self.r - other.r }
+    }
+}
+
+impl std::ops::Mul<isize> for Hex {
+    type Output = Hex;
+
+    fn mul(self, k: isize) -> Hex {
+        Hex { q: self.q * k, r: self.r * k }
+    }
+}
+
+/// A rectangular map of hexes, stored as offset rows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexMap<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+    layout: OffsetLayout,
+}
+
+impl<T> HexMap<T> {
+    /// Construct a map by calling a function with each hex
+    pub fn from_fn<F: Fn(Hex) -> T>(width: usize, height: usize, layout: OffsetLayout, func: F) -> Self {
+        let mut data = Vec::with_capacity(width*height);
+        for row in 0..height {
+            for col in 0..width {
+                data.push((func)(Hex::from_offset((col as isize, row as isize), layout)));
+            }
+        }
+        Self { data, width, height, layout }
+    }
+
+    /// Parse a map from ASCII art with alternate rows indented by one character
+    ///
+    /// Cells are the non-space characters of each line, and are converted with `func`. If the
+    /// first line is indented then even rows are treated as shifted right, otherwise odd rows are.
+    pub fn parse<F: Fn(char) -> T>(text: &str, func: F) -> Result<Self> {
+        let lines = grid_lines(text.lines()).collect::<Vec<_>>();
+        anyhow::ensure!(!lines.is_empty(), "Hex map is empty");
+
+        let base = lines.iter().map(|(_, indent, _)| *indent).min().unwrap();
+        let layout = if lines[0].1 > base { OffsetLayout::EvenR } else { OffsetLayout::OddR };
+
+        let mut data = Vec::new();
+        let mut width = None;
+        for (row, &(line, indent, cells)) in lines.iter().enumerate() {
+            let shifted = match layout {
+                OffsetLayout::OddR => row % 2 == 1,
+                OffsetLayout::EvenR => row % 2 == 0,
+            };
+            anyhow::ensure!((indent > base) == shifted,
+                            "Hex map row on line {} is not offset consistently with its neighbors",
+                            line);
+
+            // rows were trimmed at ASCII whitespace boundaries, so they're still valid UTF-8
+            let cells = std::str::from_utf8(cells).unwrap();
+            let len = data.len();
+            data.extend(cells.chars().filter(|c| !c.is_whitespace()).map(&func));
+            check_width(&mut width, line, data.len() - len)?;
+        }
+
+        Ok(Self { data, width: width.unwrap(), height: lines.len(), layout })
+    }
+
+    /// Get the number of hexes in each row
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the offset layout used by this map
+    pub fn layout(&self) -> OffsetLayout {
+        self.layout
+    }
+
+    /// Get the index of a hex in the data array, if it lies inside the map
+    #[inline]
+    fn index(&self, hex: Hex) -> Option<usize> {
+        let (col, row) = hex.to_offset(self.layout);
+        let (col, row) = (usize::try_from(col).ok()?, usize::try_from(row).ok()?);
+        (col < self.width && row < self.height).then_some(row*self.width + col)
+    }
+
+    /// Check whether a hex lies inside the map
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.index(hex).is_some()
+    }
+
+    /// Get the value at a hex, if it lies inside the map
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        Some(&self.data[self.index(hex)?])
+    }
+
+    /// Get a mutable reference to the value at a hex, if it lies inside the map
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        let idx = self.index(hex)?;
+        Some(&mut self.data[idx])
+    }
+
+    /// Set the value at a hex
+    ///
+    /// # Panics
+    /// Panics if the hex is not inside the map.
+    pub fn set(&mut self, hex: Hex, val: T) {
+        *self.get_mut(hex)
+             .unwrap_or_else(|| panic!("Attempted to access hex ({}, {}) outside map", hex.q, hex.r))
+            = val;
+    }
+
+    /// Get a reference to a specific hex on the map
+    ///
+    /// # Panics
+    /// Panics if the hex is not inside the map.
+    pub fn point(&self, hex: Hex) -> HexPoint<'_, T> {
+        let index = self.index(hex)
+                   .unwrap_or_else(|| panic!("Attempted to access hex ({}, {}) outside map", hex.q, hex.r));
+        HexPoint { map: self, hex, index }
+    }
+
+    /// Iterate over every hex on the map, row by row
+    pub fn points(&self) -> impl Iterator<Item=HexPoint<'_, T>> {
+        (0..self.data.len()).map(move |index| {
+            let offset = ((index % self.width) as isize, (index / self.width) as isize);
+            HexPoint { map: self, hex: Hex::from_offset(offset, self.layout), index }
+        })
+    }
+}
+
+/// A reference to a specific hex on a map
+pub struct HexPoint<'m, T> {
+    map: &'m HexMap<T>,
+    hex: Hex,
+    index: usize,
+}
+
+impl<T> Clone for HexPoint<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for HexPoint<'_, T> {}
+
+impl<'m, T> HexPoint<'m, T> {
+    /// Get the coordinates of this hex
+    pub fn hex(&self) -> Hex {
+        self.hex
+    }
+
+    /// Get the adjacent hex in a given direction, if it exists
+    pub fn step(&self, dir: HexDirection) -> Option<Self> {
+        let hex = self.hex.step(dir);
+        Some(Self { map: self.map, hex, index: self.map.index(hex)? })
+    }
+
+    /// Iterate over neighboring hexes
+    pub fn neighbors(&self) -> impl Iterator<Item=HexPoint<'m, T>> {
+        let point = *self;
+        HexDirection::ALL.into_iter().filter_map(move |dir| point.step(dir))
+    }
+
+    /// Iterate over neighboring hexes, along with the direction taken to reach each one
+    pub fn neighbors_dir(&self) -> impl Iterator<Item=(HexDirection, HexPoint<'m, T>)> {
+        let point = *self;
+        HexDirection::ALL.into_iter().filter_map(move |dir| Some((dir, point.step(dir)?)))
+    }
+}
+
+impl<T> std::ops::Deref for HexPoint<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.map.data[self.index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn coordinates() {
+        let origin = Hex::new(2, -1);
+        assert_eq!(origin.cube(), (2, -1, -1));
+        assert_eq!(origin.distance(Hex::new(-1, 2)), 3);
+        assert!(origin.neighbors().all(|n| n.distance(origin) == 1));
+        assert_eq!(HexDirection::East.rotate_ccw(), HexDirection::NorthEast);
+        assert_eq!(HexDirection::East.rotate_cw(), HexDirection::SouthEast);
+        assert_eq!(HexDirection::NorthWest.opposite(), HexDirection::SouthEast);
+
+        assert_eq!(origin.ring(0).collect::<Vec<_>>(), vec![origin]);
+        let ring = origin.ring(2).collect::<Vec<_>>();
+        assert_eq!(ring.len(), 12);
+        assert!(ring.iter().all(|h| h.distance(origin) == 2));
+        assert!(ring.windows(2).all(|w| w[0].distance(w[1]) == 1));
+
+        let spiral = origin.spiral(3).collect::<std::collections::HashSet<_>>();
+        assert_eq!(spiral.len(), 37);
+
+        for layout in [OffsetLayout::OddR, OffsetLayout::EvenR] {
+            for offset in [(0, 0), (3, 1), (-2, -3), (5, 4)] {
+                assert_eq!(Hex::from_offset(offset, layout).to_offset(layout), offset);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_map() {
+        let map = HexMap::parse("a b c\n d e f\ng h i\n", |c| c).unwrap();
+        assert_eq!(map.layout(), OffsetLayout::OddR);
+        assert_eq!((map.width(), map.height()), (3, 3));
+
+        // in odd-r layout, `e` sits between `b` and `c` above and `h` and `i` below
+        let e = map.points().find(|p| **p == 'e').unwrap();
+        let mut around = e.neighbors().map(|p| *p).collect::<Vec<_>>();
+        around.sort();
+        assert_eq!(around, vec!['b', 'c', 'd', 'f', 'h', 'i']);
+
+        let a = map.point(Hex::ZERO);
+        assert_eq!(a.neighbors_dir().map(|(d, p)| (d, *p)).collect::<Vec<_>>(),
+                   vec![(HexDirection::East, 'b'), (HexDirection::SouthEast, 'd')]);
+
+        let shifted = HexMap::parse(" a b\nc d\n", |c| c).unwrap();
+        assert_eq!(shifted.layout(), OffsetLayout::EvenR);
+        assert_eq!(shifted.point(Hex::ZERO).neighbors().map(|p| *p).collect::<Vec<_>>(),
+                   vec!['b', 'c', 'd']);
+
+        let err = HexMap::parse("a b\n\nc d\n", |c| c).unwrap_err();
+        assert_eq!(err.to_string(),
+                   "Hex map row on line 3 is not offset consistently with its neighbors");
+        let err = HexMap::parse("a b\n\n c\n", |c| c).unwrap_err();
+        assert_eq!(err.to_string(), "Grid row on line 3 has width 1, but previous rows have width 2");
+    }
+}
@@ -0,0 +1,248 @@
+//! Export of grids as binary PPM (color) and PGM (grayscale) images
+//!
+//! Both formats are trivial to write by hand and are readable by most image viewers and
+//! converters, so no image library is needed.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::Grid;
+
+/// An RGB color
+pub type Rgb = [u8; 3];
+
+/// Map a value in `0.0..=1.0` to a color on a blue-green-red heatmap scale
+///
+/// Values outside that range are clamped.
+pub fn heat_color(t: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [[0, 0, 255], [0, 255, 255], [0, 255, 0], [255, 255, 0], [255, 0, 0]];
+
+    let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) } * (STOPS.len() - 1) as f64;
+    let idx = (t as usize).min(STOPS.len() - 2);
+    let frac = t - idx as f64;
+    std::array::from_fn(|c| {
+        let (a, b) = (STOPS[idx][c] as f64, STOPS[idx + 1][c] as f64);
+        (a + (b - a)*frac).round() as u8
+    })
+}
+
+impl<T> Grid<T> {
+    /// Write the image header and pixel rows, with each cell drawn as a `scale`-pixel square
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    fn write_image<W, F, const N: usize>(
+        &self,
+        out: &mut W,
+        magic: &str,
+        scale: usize,
+        pixel: F,
+    ) -> io::Result<()>
+    where W: Write + ?Sized,
+          F: Fn(&T) -> [u8; N],
+    {
+        assert!(scale > 0, "Image scale must be non-zero");
+        write!(out, "{}\n{} {}\n255\n", magic, self.width*scale, self.height*scale)?;
+
+        let mut line = Vec::with_capacity(self.width*scale*N);
+        for row in self.data.chunks(self.width.max(1)) {
+            line.clear();
+            for cell in row {
+                let px = (pixel)(cell);
+                for _ in 0..scale {
+                    line.extend_from_slice(&px);
+                }
+            }
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the grid as a binary PPM image, coloring each cell with a given function
+    ///
+    /// Each cell is drawn as a square `scale` pixels across.
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    pub fn write_ppm<W, F>(&self, out: &mut W, scale: usize, color: F) -> io::Result<()>
+    where W: Write + ?Sized,
+          F: Fn(&T) -> Rgb,
+    {
+        self.write_image(out, "P6", scale, color)
+    }
+
+    /// Write the grid as a binary PGM image, shading each cell with a given function
+    ///
+    /// Each cell is drawn as a square `scale` pixels across.
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    pub fn write_pgm<W, F>(&self, out: &mut W, scale: usize, gray: F) -> io::Result<()>
+    where W: Write + ?Sized,
+          F: Fn(&T) -> u8,
+    {
+        self.write_image(out, "P5", scale, |c| [(gray)(c)])
+    }
+
+    /// Write the grid as a PPM heatmap of numeric values
+    ///
+    /// Values are scaled between the smallest and largest in the grid. Cells for which `value`
+    /// returns `None` are drawn in black.
+    pub fn write_heatmap<W, F>(&self, out: &mut W, scale: usize, value: F) -> io::Result<()>
+    where W: Write + ?Sized,
+          F: Fn(&T) -> Option<f64>,
+    {
+        let (min, max) = self.data.iter()
+            .filter_map(&value)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let range = if max > min { max - min } else { 1. };
+
+        self.write_ppm(out, scale, |c| match (value)(c) {
+            Some(v) => heat_color((v - min) / range),
+            None => [0, 0, 0],
+        })
+    }
+
+    /// Save the grid as a PPM image file
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    pub fn save_ppm<P, F>(&self, path: P, scale: usize, color: F) -> io::Result<()>
+    where P: AsRef<Path>,
+          F: Fn(&T) -> Rgb,
+    {
+        save(path.as_ref(), |out| self.write_ppm(out, scale, color))
+    }
+
+    /// Save the grid as a PGM image file
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    pub fn save_pgm<P, F>(&self, path: P, scale: usize, gray: F) -> io::Result<()>
+    where P: AsRef<Path>,
+          F: Fn(&T) -> u8,
+    {
+        save(path.as_ref(), |out| self.write_pgm(out, scale, gray))
+    }
+}
+
+/// Create a file and fill it through a buffered writer
+fn save<F: FnOnce(&mut dyn Write) -> io::Result<()>>(path: &Path, write: F) -> io::Result<()> {
+    let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+    (write)(&mut out)?;
+    out.flush()
+}
+
+/// Writer for a numbered sequence of image files, one per simulation step
+///
+/// Frames are named `<prefix>_00000.ppm`, `<prefix>_00001.ppm` and so on (or `.pgm` for
+/// grayscale frames), which is the pattern expected by tools like `ffmpeg -i prefix_%05d.ppm`.
+#[derive(Clone, Debug)]
+pub struct FrameWriter {
+    dir: PathBuf,
+    prefix: String,
+    scale: usize,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Create a frame writer saving into a given directory, creating it if needed
+    pub fn new<P: AsRef<Path>>(dir: P, prefix: &str) -> io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir: dir.as_ref().to_owned(), prefix: prefix.to_owned(), scale: 1, next: 0 })
+    }
+
+    /// Set the size in pixels of each grid cell
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    pub fn scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "Image scale must be non-zero");
+        self.scale = scale;
+        self
+    }
+
+    /// Get the number of frames written so far
+    pub fn frames(&self) -> usize {
+        self.next
+    }
+
+    /// Save the next frame as a PPM image, returning the path it was written to
+    pub fn write_ppm<T, F: Fn(&T) -> Rgb>(&mut self, grid: &Grid<T>, color: F) -> io::Result<PathBuf> {
+        let path = self.frame_path("ppm");
+        grid.save_ppm(&path, self.scale, color)?;
+        self.next += 1;
+        Ok(path)
+    }
+
+    /// Save the next frame as a PGM image, returning the path it was written to
+    pub fn write_pgm<T, F: Fn(&T) -> u8>(&mut self, grid: &Grid<T>, gray: F) -> io::Result<PathBuf> {
+        let path = self.frame_path("pgm");
+        grid.save_pgm(&path, self.scale, gray)?;
+        self.next += 1;
+        Ok(path)
+    }
+
+    /// Get the path of the next frame, with a given file extension
+    fn frame_path(&self, ext: &str) -> PathBuf {
+        self.dir.join(format!("{}_{:05}.{}", self.prefix, self.next, ext))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let grid = Grid::from_data(vec![0u8, 200], 2);
+
+        let mut pgm = Vec::new();
+        grid.write_pgm(&mut pgm, 2, |v| *v).unwrap();
+        assert_eq!(pgm, b"P5\n4 2\n255\n\x00\x00\xc8\xc8\x00\x00\xc8\xc8");
+
+        let mut ppm = Vec::new();
+        grid.write_ppm(&mut ppm, 1, |v| [*v, 0, 255 - *v]).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\xff\xc8\x00\x37");
+    }
+
+    #[test]
+    #[should_panic(expected = "Image scale must be non-zero")]
+    fn zero_scale() {
+        let grid = Grid::filled(1, 1, 0u8);
+        let _ = grid.write_pgm(&mut Vec::new(), 0, |v| *v);
+    }
+
+    #[test]
+    fn heatmap() {
+        assert_eq!(heat_color(0.), [0, 0, 255]);
+        assert_eq!(heat_color(0.5), [0, 255, 0]);
+        assert_eq!(heat_color(2.), [255, 0, 0]);
+
+        let grid = Grid::from_data(vec![Some(3), None, Some(7)], 3);
+        let mut out = Vec::new();
+        grid.write_heatmap(&mut out, 1, |d| d.map(f64::from)).unwrap();
+        assert_eq!(&out[11..], &[0, 0, 255, 0, 0, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn frames() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-frames-{}", std::process::id()));
+        let mut frames = FrameWriter::new(&dir, "life").unwrap().scale(3);
+
+        let grid = Grid::filled(2, 2, true);
+        frames.write_ppm(&grid, |_| [255; 3]).unwrap();
+        let second = frames.write_ppm(&grid, |_| [0; 3]).unwrap();
+
+        assert_eq!(frames.frames(), 2);
+        assert_eq!(second, dir.join("life_00001.ppm"));
+        assert_eq!(std::fs::metadata(&second).unwrap().len(), 11 + 6*6*3);
+
+        let third = frames.write_pgm(&grid, |_| 128).unwrap();
+        assert_eq!(third, dir.join("life_00002.pgm"));
+        assert_eq!(std::fs::metadata(&third).unwrap().len(), 11 + 6*6);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+//! Parsing grids from text
+
+use anyhow::{Context, Result};
+
+use super::{Grid, Topology};
+
+/// Iterate over the non-blank lines of some text, along with their 1-based line numbers
+///
+/// Surrounding whitespace is trimmed from each line. The number of bytes trimmed from the start
+/// is returned too, so that columns can be reported relative to the original line.
+pub(super) fn grid_lines<'a, L: AsRef<[u8]> + ?Sized + 'a>(
+    lines: impl Iterator<Item=&'a L>,
+) -> impl Iterator<Item=(usize, usize, &'a [u8])> {
+    lines.enumerate()
+         .map(|(i, l)| {
+             let l = l.as_ref();
+             let indent = l.len() - l.trim_ascii_start().len();
+             (i + 1, indent, l.trim_ascii())
+         })
+         .filter(|(_, _, l)| !l.is_empty())
+}
+
+/// Check that a row has the same width as the ones before it
+pub(super) fn check_width(width: &mut Option<usize>, line: usize, len: usize) -> Result<()> {
+    let expected = *width.get_or_insert(len);
+    anyhow::ensure!(len == expected,
+                    "Grid row on line {} has width {}, but previous rows have width {}",
+                    line, len, expected);
+    Ok(())
+}
+
+/// Build a parsed grid from its cells and row width, where no width means there were no rows
+fn assemble<T>(data: Vec<T>, width: Option<usize>) -> Grid<T> {
+    match width {
+        Some(width) => Grid::from_data(data, width),
+        None => Grid { data, width: 0, height: 0, topology: Topology::Bounded },
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid from text, converting each character with a fallible closure
+    ///
+    /// Blank lines and surrounding whitespace are ignored. Errors from `func` and rows with
+    /// mismatched widths are reported with the 1-based line and column of the problem.
+    pub fn parse_with<F: FnMut(char) -> Result<T>>(text: &str, mut func: F) -> Result<Self> {
+        let mut data = Vec::new();
+        let mut width = None;
+
+        for (line, indent, row) in grid_lines(text.lines()) {
+            // rows were trimmed at ASCII whitespace boundaries, so they're still valid UTF-8
+            let row = std::str::from_utf8(row).unwrap();
+
+            let start = data.len();
+            for (col, c) in row.chars().enumerate() {
+                let cell = (func)(c).with_context(|| {
+                    format!("Invalid cell {:?} at line {}, column {}", c, line, indent + col + 1)
+                })?;
+                data.push(cell);
+            }
+            check_width(&mut width, line, data.len() - start)?;
+        }
+
+        Ok(assemble(data, width))
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a grid of raw bytes, without any per-character conversion
+    ///
+    /// Blank lines and surrounding whitespace are ignored, as with [`Grid::parse_with`].
+    pub fn from_bytes(text: &[u8]) -> Result<Self> {
+        let mut data = Vec::with_capacity(text.len());
+        let mut width = None;
+
+        for (line, _, row) in grid_lines(text.split(|b| *b == b'\n')) {
+            check_width(&mut width, line, row.len())?;
+            data.extend_from_slice(row);
+        }
+
+        Ok(assemble(data, width))
+    }
+}
+
+impl<T: TryFrom<char>> std::str::FromStr for Grid<T>
+where anyhow::Error: From<T::Error>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with(s, |c| Ok(T::try_from(c)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Digit(u8);
+
+    impl TryFrom<char> for Digit {
+        type Error = anyhow::Error;
+
+        fn try_from(c: char) -> Result<Self> {
+            Ok(Digit(c.to_digit(10).context("Not a digit")? as u8))
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let grid: Grid<Digit> = "12\n34\n\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(*grid.get((1, 1)), Digit(4));
+
+        let err = "12\n\n3x\n".parse::<Grid<Digit>>().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid cell 'x' at line 3, column 2");
+        assert_eq!(err.root_cause().to_string(), "Not a digit");
+
+        let err = "123\n  4x6\n".parse::<Grid<Digit>>().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid cell 'x' at line 2, column 4");
+
+        let err = Grid::parse_with("ab\nabc\n", Ok).unwrap_err();
+        assert_eq!(err.to_string(), "Grid row on line 2 has width 3, but previous rows have width 2");
+
+        let empty = Grid::parse_with("\n", Ok).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(Grid::from_bytes(b"").unwrap().width(), 0);
+    }
+
+    #[test]
+    fn bytes() {
+        let grid = Grid::from_bytes(b"#.#\r\n..#\n").unwrap();
+        assert_eq!(grid, Grid::parse_with("#.#\n..#", |c| Ok(c as u8)).unwrap());
+        assert!(Grid::from_bytes(b"##\n#\n").is_err());
+    }
+}
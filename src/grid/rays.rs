@@ -0,0 +1,205 @@
+//! Straight-line traversal of grids
+
+use super::{Direction, Grid, GridPoint};
+
+/// Iterator over the points reached by repeatedly applying a fixed step from a start point
+///
+/// Rays stop at the edge of a bounded grid. On a [`Torus`](super::Topology::Torus) they wrap
+/// around instead, ending just before they would return to their start point.
+pub struct Ray<'g, T> {
+    next: Option<GridPoint<'g, T>>,
+    step: (isize, isize),
+    origin: usize,
+}
+
+impl<T> Clone for Ray<'_, T> {
+    fn clone(&self) -> Self {
+        Self { next: self.next, step: self.step, origin: self.origin }
+    }
+}
+
+impl<'g, T> Ray<'g, T> {
+    /// Yield points up to and including the first one matching a predicate
+    ///
+    /// This is the usual "what's the first thing visible in this direction" query; use
+    /// [`Iterator::take_while`] to stop before the matching point instead.
+    pub fn until<F: FnMut(&GridPoint<'g, T>) -> bool>(
+        self,
+        mut stop: F,
+    ) -> impl Iterator<Item=GridPoint<'g, T>> {
+        let mut done = false;
+        self.take_while(move |p| {
+            let keep = !done;
+            done = done || (stop)(p);
+            keep
+        })
+    }
+}
+
+impl<'g, T> Iterator for Ray<'g, T> {
+    type Item = GridPoint<'g, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next?;
+        self.next = cur.offset(self.step).filter(|p| p.index != self.origin);
+        Some(cur)
+    }
+}
+
+impl<'g, T> GridPoint<'g, T> {
+    /// Walk away from this point in a given direction until reaching the edge of the grid
+    ///
+    /// The start point itself is not included; see [`GridPoint::ray_from`].
+    pub fn ray(&self, dir: Direction) -> Ray<'g, T> {
+        self.ray_by(dir.offset())
+    }
+
+    /// Walk away from this point by a fixed step until reaching the edge of the grid
+    ///
+    /// The start point itself is not included; see [`GridPoint::ray_by_from`].
+    ///
+    /// # Panics
+    /// Panics if the step is zero.
+    pub fn ray_by(&self, step: (isize, isize)) -> Ray<'g, T> {
+        let mut ray = self.ray_by_from(step);
+        ray.next();
+        ray
+    }
+
+    /// Walk in a given direction starting with this point, until reaching the edge of the grid
+    pub fn ray_from(&self, dir: Direction) -> Ray<'g, T> {
+        self.ray_by_from(dir.offset())
+    }
+
+    /// Walk by a fixed step starting with this point, until reaching the edge of the grid
+    ///
+    /// # Panics
+    /// Panics if the step is zero.
+    pub fn ray_by_from(&self, step: (isize, isize)) -> Ray<'g, T> {
+        assert!(step != (0, 0), "Ray step must be non-zero");
+        Ray { next: Some(*self), step, origin: self.index }
+    }
+}
+
+/// Iterator over the points on a straight line between two cells, using Bresenham's algorithm
+pub struct Line<'g, T> {
+    grid: &'g Grid<T>,
+    cur: (isize, isize),
+    end: (isize, isize),
+    delta: (isize, isize),
+    sign: (isize, isize),
+    err: isize,
+    remaining: usize,
+}
+
+impl<T> Grid<T> {
+    /// Iterate over the cells on a straight line between two points, inclusive of both ends
+    ///
+    /// # Panics
+    /// Panics if either end is not inside the grid.
+    pub fn line(&self, from: (usize, usize), to: (usize, usize)) -> Line<'_, T> {
+        for pos in [from, to] {
+            assert!(pos.0 < self.width && pos.1 < self.height,
+                    "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
+        }
+
+        let (x0, y0) = (from.0 as isize, from.1 as isize);
+        let (x1, y1) = (to.0 as isize, to.1 as isize);
+        let delta = ((x1 - x0).abs(), -(y1 - y0).abs());
+        Line {
+            grid: self,
+            cur: (x0, y0),
+            end: (x1, y1),
+            delta,
+            sign: ((x1 - x0).signum(), (y1 - y0).signum()),
+            err: delta.0 + delta.1,
+            remaining: delta.0.max(-delta.1) as usize + 1,
+        }
+    }
+}
+
+impl<'g, T> Iterator for Line<'g, T> {
+    type Item = GridPoint<'g, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let out = self.grid.point((self.cur.0 as usize, self.cur.1 as usize));
+        if self.cur != self.end {
+            let e2 = 2*self.err;
+            if e2 >= self.delta.1 {
+                self.err += self.delta.1;
+                self.cur.0 += self.sign.0;
+            }
+            if e2 <= self.delta.0 {
+                self.err += self.delta.0;
+                self.cur.1 += self.sign.1;
+            }
+        }
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Line<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Topology;
+
+    fn values<'g>(points: impl Iterator<Item=GridPoint<'g, usize>>) -> Vec<usize> {
+        points.map(|p| *p).collect()
+    }
+
+    #[test]
+    fn rays() {
+        let grid = Grid::from_fn(5, 4, |x, y| x + 10*y);
+        let start = grid.point((1, 1));
+
+        assert_eq!(values(start.ray(Direction::Right)), vec![12, 13, 14]);
+        assert_eq!(values(start.ray_from(Direction::Up)), vec![11, 1]);
+        assert_eq!(values(start.ray(Direction::DownRight)), vec![22, 33]);
+        assert_eq!(values(start.ray(Direction::UpLeft)), vec![0]);
+        assert_eq!(values(start.ray_by((1, 2))), vec![32]);
+        assert_eq!(values(grid.point((0, 0)).ray_by_from((2, 1))), vec![0, 12, 24]);
+
+        assert_eq!(values(start.ray(Direction::Right).until(|p| **p % 2 == 0)), vec![12]);
+        assert_eq!(values(start.ray(Direction::Right).until(|_| false)), vec![12, 13, 14]);
+        assert_eq!(start.ray(Direction::Down).take_while(|p| **p < 30).count(), 1);
+    }
+
+    #[test]
+    fn torus_rays() {
+        let grid = Grid::from_fn(5, 4, |x, y| x + 10*y).with_topology(Topology::Torus);
+        let start = grid.point((1, 1));
+
+        assert_eq!(values(start.ray(Direction::Right)), vec![12, 13, 14, 10]);
+        assert_eq!(values(start.ray_from(Direction::Up)), vec![11, 1, 31, 21]);
+        assert_eq!(start.ray(Direction::DownRight).count(), 19);
+        assert_eq!(values(start.ray(Direction::Left).until(|p| **p == 14)), vec![10, 14]);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::filled(8, 8, 0);
+        let coords = |from, to| grid.line(from, to).map(|p| p.coords()).collect::<Vec<_>>();
+
+        assert_eq!(coords((0, 0), (5, 2)), vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]);
+        assert_eq!(coords((2, 6), (2, 3)), vec![(2, 6), (2, 5), (2, 4), (2, 3)]);
+        assert_eq!(coords((4, 4), (4, 4)), vec![(4, 4)]);
+        assert_eq!(coords((7, 0), (0, 7)).len(), 8);
+
+        let line = grid.line((1, 7), (6, 0));
+        assert_eq!(line.len(), 8);
+        let points = line.map(|p| p.coords()).collect::<Vec<_>>();
+        assert_eq!(points.last(), Some(&(6, 0)));
+        assert!(points.windows(2).all(|w| w[0].0.abs_diff(w[1].0) <= 1 && w[0].1.abs_diff(w[1].1) == 1));
+    }
+}
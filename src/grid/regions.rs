@@ -0,0 +1,151 @@
+//! Flood fill and connected-component labeling
+
+use std::collections::VecDeque;
+
+use super::{Grid, GridPoint, Neighborhood};
+
+/// Connected regions of a grid
+#[derive(Clone, Debug)]
+pub struct Components {
+    /// Region index of each cell, or `None` for cells excluded from labeling
+    pub labels: Grid<Option<usize>>,
+
+    /// Points making up each region, indexed by label
+    pub regions: Vec<Vec<(usize, usize)>>,
+}
+
+impl<T> Grid<T> {
+    /// Find all points in the region containing `start`
+    ///
+    /// Two adjacent points are in the same region if `same_region(from, to)` returns true. Points
+    /// are returned in breadth-first order starting with `start`.
+    pub fn flood_fill<F>(
+        &self,
+        start: (usize, usize),
+        neighborhood: Neighborhood,
+        mut same_region: F,
+    ) -> Vec<(usize, usize)>
+    where F: FnMut(GridPoint<'_, T>, GridPoint<'_, T>) -> bool,
+    {
+        let mut visited = Grid::filled_like(self, None);
+        self.fill_region(start, 0, neighborhood, &mut visited, &mut same_region)
+    }
+
+    /// Label every connected region of the grid
+    ///
+    /// Only cells for which `include` returns true are labeled; two adjacent included cells belong
+    /// to the same region if `same_region(from, to)` returns true. Labels are assigned in
+    /// row-major order of each region's first cell.
+    pub fn components<P, F>(
+        &self,
+        neighborhood: Neighborhood,
+        include: P,
+        mut same_region: F,
+    ) -> Components
+    where P: Fn(&T) -> bool,
+          F: FnMut(GridPoint<'_, T>, GridPoint<'_, T>) -> bool,
+    {
+        let mut labels = Grid::filled_like(self, None);
+        let mut regions = Vec::new();
+
+        for idx in 0..self.data.len() {
+            if labels.data[idx].is_some() || !(include)(&self.data[idx]) {
+                continue;
+            }
+
+            let start = (idx % self.width, idx / self.width);
+            let mut same = |a: GridPoint<'_, T>, b: GridPoint<'_, T>| {
+                (include)(&b) && (same_region)(a, b)
+            };
+            let region = self.fill_region(start, regions.len(), neighborhood, &mut labels, &mut same);
+            regions.push(region);
+        }
+
+        Components { labels, regions }
+    }
+
+    /// Breadth-first fill of unlabeled cells from a start point, marking them with a label
+    fn fill_region<F>(
+        &self,
+        start: (usize, usize),
+        label: usize,
+        neighborhood: Neighborhood,
+        labels: &mut Grid<Option<usize>>,
+        same_region: &mut F,
+    ) -> Vec<(usize, usize)>
+    where F: FnMut(GridPoint<'_, T>, GridPoint<'_, T>) -> bool,
+    {
+        let mut out = vec![start];
+        let mut queue = VecDeque::from([self.point(start)]);
+        labels.set(start, Some(label));
+
+        while let Some(cur) = queue.pop_front() {
+            for dir in neighborhood.directions() {
+                let Some(next) = cur.step(*dir) else { continue; };
+                if labels.get(next.coords()).is_some() || !(same_region)(cur, next) {
+                    continue;
+                }
+
+                labels.set(next.coords(), Some(label));
+                out.push(next.coords());
+                queue.push_back(next);
+            }
+        }
+
+        out
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Label every region of adjacent cells with equal values
+    pub fn components_eq(&self, neighborhood: Neighborhood) -> Components {
+        self.components(neighborhood, |_| true, |a, b| *a == *b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn plot() -> Grid<char> {
+        const PLOT: &[&str] = &[
+            "AAB.",
+            "ABB.",
+            ".B.A",
+            "A..A",
+        ];
+        Grid::from_data(PLOT.iter().flat_map(|r| r.chars()).collect(), 4)
+    }
+
+    #[test]
+    fn flood() {
+        let grid = plot();
+
+        let mut region = grid.flood_fill((0, 0), Neighborhood::Four, |a, b| *a == *b);
+        region.sort();
+        assert_eq!(region, vec![(0, 0), (0, 1), (1, 0)]);
+
+        let region = grid.flood_fill((3, 0), Neighborhood::Four, |a, b| *a == *b);
+        assert_eq!(region, vec![(3, 0), (3, 1)]);
+        let region = grid.flood_fill((3, 0), Neighborhood::Eight, |a, b| *a == *b);
+        assert_eq!(region.len(), 6);
+    }
+
+    #[test]
+    fn labeling() {
+        let grid = plot();
+
+        let all = grid.components_eq(Neighborhood::Four);
+        assert_eq!(all.regions.len(), 7);
+        assert_eq!(all.regions.iter().map(|r| r.len()).sum::<usize>(), 16);
+        assert_eq!(*all.labels.get((0, 0)), Some(0));
+        assert_eq!(*all.labels.get((2, 0)), Some(1));
+        assert_eq!(*all.labels.get((1, 2)), Some(1));
+
+        let letters = grid.components(Neighborhood::Eight, |c| *c != '.', |a, b| *a == *b);
+        assert_eq!(letters.regions.len(), 4);
+        assert_eq!(*letters.labels.get((3, 0)), None);
+        assert_eq!(letters.labels.get((3, 2)), letters.labels.get((3, 3)));
+        assert_ne!(letters.labels.get((0, 3)), letters.labels.get((0, 0)));
+    }
+}
@@ -0,0 +1,201 @@
+//! Text rendering of grids, with optional ANSI color overlays
+
+use std::fmt;
+
+use super::{Direction, Grid};
+
+/// Cell types with a natural single-character representation
+pub trait Glyph {
+    /// Get the character used to draw this cell
+    fn glyph(&self) -> char;
+}
+
+impl Glyph for bool {
+    fn glyph(&self) -> char {
+        if *self { '#' } else { ' ' }
+    }
+}
+
+impl Glyph for char {
+    fn glyph(&self) -> char {
+        *self
+    }
+}
+
+impl Glyph for u8 {
+    fn glyph(&self) -> char {
+        *self as char
+    }
+}
+
+impl<G: Glyph> Glyph for Option<G> {
+    fn glyph(&self) -> char {
+        self.as_ref().map_or(' ', G::glyph)
+    }
+}
+
+/// Terminal colors available for highlighting
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    /// Get the ANSI escape sequence selecting this color as a bold foreground
+    fn escape(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[1;31m",
+            Color::Green => "\x1b[1;32m",
+            Color::Yellow => "\x1b[1;33m",
+            Color::Blue => "\x1b[1;34m",
+            Color::Magenta => "\x1b[1;35m",
+            Color::Cyan => "\x1b[1;36m",
+        }
+    }
+}
+
+/// ANSI escape sequence resetting all attributes
+const RESET: &str = "\x1b[0m";
+
+/// A highlighted cell, with an optional replacement glyph
+type Mark = (Color, Option<char>);
+
+/// A grid prepared for rendering as text
+///
+/// This implements [`Display`](fmt::Display), so it can be written to any [`fmt::Write`] or
+/// [`std::io::Write`] with `write!`. Each row is terminated by a newline.
+pub struct Render<'g, T, F> {
+    grid: &'g Grid<T>,
+    glyph: F,
+    marks: Vec<Option<Mark>>,
+}
+
+impl<T> Grid<T> {
+    /// Prepare the grid for rendering, drawing each cell with a given function
+    pub fn render_with<F: Fn(&T) -> char>(&self, glyph: F) -> Render<'_, T, F> {
+        Render { grid: self, glyph, marks: Vec::new() }
+    }
+}
+
+impl<T: Glyph> Grid<T> {
+    /// Prepare the grid for rendering, drawing each cell with its [`Glyph`]
+    pub fn render(&self) -> Render<'_, T, fn(&T) -> char> {
+        self.render_with(T::glyph)
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Render<'_, T, F> {
+    /// Mark a single cell
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the grid.
+    fn mark(&mut self, pos: (usize, usize), mark: Mark) {
+        assert!(pos.0 < self.grid.width && pos.1 < self.grid.height,
+                "Attempted to access position ({}, {}) outside grid", pos.0, pos.1);
+        if self.marks.is_empty() {
+            self.marks.resize(self.grid.data.len(), None);
+        }
+        self.marks[pos.1*self.grid.width + pos.0] = Some(mark);
+    }
+
+    /// Draw a set of cells in a given color
+    ///
+    /// Later highlights take priority over earlier ones.
+    ///
+    /// # Panics
+    /// Panics if any of the points are not inside the grid.
+    pub fn highlight<I: IntoIterator<Item=(usize, usize)>>(mut self, points: I, color: Color) -> Self {
+        for pos in points {
+            self.mark(pos, (color, None));
+        }
+        self
+    }
+
+    /// Draw a path in a given color, replacing each step with an arrow towards the next one
+    ///
+    /// The final cell keeps its own glyph, as do cells followed by a step to a non-adjacent cell.
+    ///
+    /// # Panics
+    /// Panics if any of the points are not inside the grid.
+    pub fn path(mut self, path: &[(usize, usize)], color: Color) -> Self {
+        for (i, &pos) in path.iter().enumerate() {
+            let arrow = path.get(i + 1).and_then(|next| {
+                let delta = (next.0 as isize - pos.0 as isize, next.1 as isize - pos.1 as isize);
+                Direction::from_offset(delta).map(|dir| match dir {
+                    Direction::Up => '^',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                    Direction::Right => '>',
+                    Direction::UpRight | Direction::DownLeft => '/',
+                    Direction::UpLeft | Direction::DownRight => '\\',
+                })
+            });
+            self.mark(pos, (color, arrow));
+        }
+        self
+    }
+
+    /// Write the rendered grid to an I/O stream
+    pub fn write_to<W: std::io::Write + ?Sized>(&self, out: &mut W) -> std::io::Result<()> {
+        write!(out, "{}", self)
+    }
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for Render<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use fmt::Write;
+
+        for (y, row) in self.grid.data.chunks(self.grid.width.max(1)).enumerate() {
+            // only emit escapes when the color changes, to keep the output compact
+            let mut active = None;
+            for (x, cell) in row.iter().enumerate() {
+                let mark = self.marks.get(y*self.grid.width + x).copied().flatten();
+                let color = mark.map(|(c, _)| c);
+                if color != active {
+                    f.write_str(color.map_or(RESET, Color::escape))?;
+                    active = color;
+                }
+                f.write_char(mark.and_then(|(_, g)| g).unwrap_or_else(|| (self.glyph)(cell)))?;
+            }
+            if active.is_some() {
+                f.write_str(RESET)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plain() {
+        let grid = Grid::from_data(vec![true, false, false, true], 2);
+        assert_eq!(grid.render().to_string(), "# \n #\n");
+        assert_eq!(grid.render_with(|b| if *b { '1' } else { '0' }).to_string(), "10\n01\n");
+
+        let mut out = Vec::new();
+        grid.map(|b| b.then_some('x')).render().write_to(&mut out).unwrap();
+        assert_eq!(out, b"x \n x\n");
+    }
+
+    #[test]
+    fn overlays() {
+        let grid = Grid::filled(3, 2, '.');
+
+        let rendered = grid.render().highlight([(0, 0), (1, 0)], Color::Red).to_string();
+        assert_eq!(rendered, "\x1b[1;31m..\x1b[0m.\n...\n");
+
+        let rendered = grid.render()
+                      .path(&[(0, 0), (0, 1), (1, 1), (2, 0)], Color::Green)
+                      .to_string();
+        assert_eq!(rendered, "\x1b[1;32mv\x1b[0m.\x1b[1;32m.\x1b[0m\n\x1b[1;32m>/\x1b[0m.\n");
+    }
+}
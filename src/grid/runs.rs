@@ -0,0 +1,190 @@
+//! Extraction of horizontal and vertical runs of matching cells
+
+use super::{Direction, Grid, GridPoint};
+
+/// Orientation of a run
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Along a row, from left to right
+    Horizontal,
+
+    /// Along a column, from top to bottom
+    Vertical,
+}
+
+impl Axis {
+    /// Map a position along a line to grid coordinates
+    #[inline]
+    fn coords(self, line: usize, offset: usize) -> (usize, usize) {
+        match self {
+            Axis::Horizontal => (offset, line),
+            Axis::Vertical => (line, offset),
+        }
+    }
+
+    /// Get the direction of travel along a run
+    pub fn direction(self) -> Direction {
+        match self {
+            Axis::Horizontal => Direction::Right,
+            Axis::Vertical => Direction::Down,
+        }
+    }
+}
+
+/// A maximal line of consecutive matching cells in a single row or column
+pub struct Run<'g, T> {
+    grid: &'g Grid<T>,
+    axis: Axis,
+    start: (usize, usize),
+    len: usize,
+}
+
+impl<T> Clone for Run<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Run<'_, T> {}
+
+impl<T> std::fmt::Debug for Run<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Run")
+         .field("axis", &self.axis)
+         .field("start", &self.start)
+         .field("len", &self.len)
+         .finish()
+    }
+}
+
+impl<'g, T> Run<'g, T> {
+    /// Get the coordinates of the first cell in the run
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// Get the number of cells in the run
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the run is empty, which is never the case for extracted runs
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the orientation of the run
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    /// Iterate over the cells in the run, in order
+    pub fn cells(&self) -> impl DoubleEndedIterator<Item=GridPoint<'g, T>> + ExactSizeIterator {
+        let run = *self;
+        (0..self.len).map(move |i| {
+            let pos = match run.axis {
+                Axis::Horizontal => (run.start.0 + i, run.start.1),
+                Axis::Vertical => (run.start.0, run.start.1 + i),
+            };
+            run.grid.point(pos)
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// Iterate over all maximal runs of cells matching a predicate along a given axis
+    ///
+    /// Runs are yielded line by line (top to bottom for rows, left to right for columns), and in
+    /// order within each line.
+    pub fn runs<F: Fn(&T) -> bool>(&self, axis: Axis, pred: F) -> impl Iterator<Item=Run<'_, T>> {
+        let (lines, len) = match axis {
+            Axis::Horizontal => (self.height, self.width),
+            Axis::Vertical => (self.width, self.height),
+        };
+
+        let mut line = 0;
+        let mut offset = 0;
+        std::iter::from_fn(move || {
+            while line < lines {
+                // skip to the start of the next run
+                while offset < len && !(pred)(self.get(axis.coords(line, offset))) {
+                    offset += 1;
+                }
+
+                if offset == len {
+                    line += 1;
+                    offset = 0;
+                    continue;
+                }
+
+                let begin = offset;
+                while offset < len && (pred)(self.get(axis.coords(line, offset))) {
+                    offset += 1;
+                }
+
+                return Some(Run {
+                    grid: self,
+                    axis,
+                    start: axis.coords(line, begin),
+                    len: offset - begin,
+                });
+            }
+            None
+        })
+    }
+
+    /// Iterate over all maximal horizontal runs of cells matching a predicate
+    pub fn row_runs<F: Fn(&T) -> bool>(&self, pred: F) -> impl Iterator<Item=Run<'_, T>> {
+        self.runs(Axis::Horizontal, pred)
+    }
+
+    /// Iterate over all maximal vertical runs of cells matching a predicate
+    pub fn col_runs<F: Fn(&T) -> bool>(&self, pred: F) -> impl Iterator<Item=Run<'_, T>> {
+        self.runs(Axis::Vertical, pred)
+    }
+
+    /// Find all runs of matching cells along an axis, along with a grid mapping each cell to the
+    /// index of the run containing it
+    pub fn runs_labeled<F: Fn(&T) -> bool>(
+        &self,
+        axis: Axis,
+        pred: F,
+    ) -> (Vec<Run<'_, T>>, Grid<Option<usize>>) {
+        let runs = self.runs(axis, pred).collect::<Vec<_>>();
+
+        let mut labels = Grid::filled_like(self, None);
+        for (idx, run) in runs.iter().enumerate() {
+            for cell in run.cells() {
+                labels.set(cell.coords(), Some(idx));
+            }
+        }
+
+        (runs, labels)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract_runs() {
+        let grid = Grid::from_data("ab.c\n...d\nef.d".lines()
+                                   .flat_map(|l| l.chars()).collect(), 4);
+        let letter = |c: &char| c.is_ascii_alphabetic();
+
+        let rows = grid.row_runs(letter)
+                  .map(|r| (r.start(), r.len(), r.cells().map(|c| *c).collect::<String>()))
+                  .collect::<Vec<_>>();
+        assert_eq!(rows, vec![((0, 0), 2, "ab".to_owned()), ((3, 0), 1, "c".to_owned()),
+                              ((3, 1), 1, "d".to_owned()), ((0, 2), 2, "ef".to_owned()),
+                              ((3, 2), 1, "d".to_owned())]);
+
+        let cols = grid.col_runs(letter).map(|r| (r.start(), r.len())).collect::<Vec<_>>();
+        assert_eq!(cols, vec![((0, 0), 1), ((0, 2), 1), ((1, 0), 1), ((1, 2), 1), ((3, 0), 3)]);
+
+        let (runs, labels) = grid.runs_labeled(Axis::Vertical, letter);
+        assert_eq!(runs.len(), 5);
+        assert_eq!(*labels.get((3, 2)), Some(4));
+        assert_eq!(*labels.get((2, 0)), None);
+    }
+}
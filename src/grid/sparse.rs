@@ -0,0 +1,275 @@
+//! Unbounded sparse grids backed by a hash map
+
+use fnv::FnvHashMap;
+
+use super::{Direction, Glyph, Grid, Pos};
+
+/// A grid with unbounded signed coordinates, storing only occupied cells
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: FnvHashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self { cells: FnvHashMap::default() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Create an empty grid
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of occupied cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Check whether no cells are occupied
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Check whether a cell is occupied
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Get the value of a cell, if it is occupied
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    /// Get a mutable reference to the value of a cell, if it is occupied
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Set the value of a cell, returning the previous value if it was occupied
+    pub fn set(&mut self, pos: Pos, val: T) -> Option<T> {
+        self.cells.insert(pos, val)
+    }
+
+    /// Clear a cell, returning its value if it was occupied
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    /// Iterate over all occupied cells in unspecified order
+    pub fn iter(&self) -> impl Iterator<Item=(Pos, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// Iterate over all occupied cells in unspecified order, with mutable access to their values
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=(Pos, &mut T)> {
+        self.cells.iter_mut().map(|(p, v)| (*p, v))
+    }
+
+    /// Get a reference to a specific point on the grid
+    pub fn point(&self, pos: Pos) -> SparsePoint<'_, T> {
+        SparsePoint { grid: self, pos }
+    }
+
+    /// Iterate over each occupied point in unspecified order
+    pub fn points(&self) -> impl Iterator<Item=SparsePoint<'_, T>> {
+        self.cells.keys().map(|pos| SparsePoint { grid: self, pos: *pos })
+    }
+
+    /// Get the smallest rectangle containing every occupied cell
+    ///
+    /// Returns the inclusive minimum and maximum corners, or `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut keys = self.cells.keys();
+        let first = *keys.next()?;
+        Some(keys.fold((first, first), |(min, max), p| {
+            (Pos::new(min.x.min(p.x), min.y.min(p.y)), Pos::new(max.x.max(p.x), max.y.max(p.y)))
+        }))
+    }
+
+    /// Build a sparse grid from the cells of a dense one for which `keep` returns true
+    ///
+    /// Cell `(x, y)` of the dense grid is stored at `Pos::new(x, y)`.
+    pub fn from_grid_filtered<F: Fn(&T) -> bool>(grid: Grid<T>, keep: F) -> Self {
+        let width = grid.width();
+        let cells = grid.into_cells().enumerate()
+                   .filter(|(_, v)| (keep)(v))
+                   .map(|(idx, v)| (Pos::from((idx % width, idx / width)), v))
+                   .collect();
+        Self { cells }
+    }
+
+    /// Display the grid's bounding box to the console using a given rendering function
+    ///
+    /// Unoccupied cells are passed to the function as `None`.
+    pub fn show_with<F: Fn(Option<&T>) -> char>(&self, func: F) {
+        match self.dense() {
+            Some(grid) => eprint!("\n{}", grid.render_with(|c| (func)(*c))),
+            None => eprintln!(),
+        }
+    }
+
+    /// Borrow the grid's bounding box as a dense grid, so it can share the dense rendering code
+    ///
+    /// Returns `None` if the grid is empty.
+    fn dense(&self) -> Option<Grid<Option<&T>>> {
+        let (min, max) = self.bounds()?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        Some(Grid::from_fn(width, height, |x, y| self.get(min + Pos::from((x, y)))))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Copy the grid's bounding box into a dense grid, filling unoccupied cells with a value
+    ///
+    /// Returns the dense grid along with the position of its top-left corner, or `None` if the
+    /// grid is empty.
+    pub fn to_grid(&self, fill: T) -> Option<(Grid<T>, Pos)> {
+        let (min, max) = self.bounds()?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+
+        let grid = Grid::from_fn(width, height, |x, y| {
+            self.get(min + Pos::from((x, y))).cloned().unwrap_or_else(|| fill.clone())
+        });
+        Some((grid, min))
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid_filtered(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item=(Pos, T)>>(iter: I) -> Self {
+        Self { cells: iter.into_iter().collect() }
+    }
+}
+
+impl std::fmt::Display for SparseGrid<bool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.dense() {
+            Some(grid) => write!(f, "\n{}", grid.render_with(|c| c.copied().glyph())),
+            None => writeln!(f),
+        }
+    }
+}
+
+/// A reference to a specific, possibly unoccupied, point on a sparse grid
+pub struct SparsePoint<'g, T> {
+    grid: &'g SparseGrid<T>,
+    pos: Pos,
+}
+
+impl<T> Clone for SparsePoint<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SparsePoint<'_, T> {}
+
+impl<'g, T> SparsePoint<'g, T> {
+    /// Get the position of this point
+    pub fn pos(&self) -> Pos {
+        self.pos
+    }
+
+    /// Get the value at this point, if it is occupied
+    pub fn get(&self) -> Option<&'g T> {
+        self.grid.get(self.pos)
+    }
+
+    /// Get the point at a given offset relative to this one
+    pub fn offset(&self, delta: (isize, isize)) -> Self {
+        Self { grid: self.grid, pos: self.pos + Pos::from(delta) }
+    }
+
+    /// Get the adjacent point in a given direction
+    pub fn step(&self, dir: Direction) -> Self {
+        Self { grid: self.grid, pos: self.pos.step(dir) }
+    }
+
+    /// Get the point to the left of this one
+    pub fn left(&self) -> Self {
+        self.step(Direction::Left)
+    }
+
+    /// Get the point to the right of this one
+    pub fn right(&self) -> Self {
+        self.step(Direction::Right)
+    }
+
+    /// Get the point above this one
+    pub fn up(&self) -> Self {
+        self.step(Direction::Up)
+    }
+
+    /// Get the point below this one
+    pub fn down(&self) -> Self {
+        self.step(Direction::Down)
+    }
+
+    /// Iterate over all eight neighboring points, occupied or not
+    pub fn neighbors(&self) -> impl Iterator<Item=SparsePoint<'g, T>> {
+        let point = *self;
+        Direction::ALL.into_iter().map(move |dir| point.step(dir))
+    }
+
+    /// Iterate over the four cardinal neighboring points, occupied or not
+    pub fn neighbors4(&self) -> impl Iterator<Item=SparsePoint<'g, T>> {
+        let point = *self;
+        Direction::CARDINAL.into_iter().map(move |dir| point.step(dir))
+    }
+
+    /// Iterate over the occupied neighboring points along with their values
+    pub fn occupied_neighbors(&self) -> impl Iterator<Item=(SparsePoint<'g, T>, &'g T)> {
+        self.neighbors().filter_map(|p| Some((p, p.get()?)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sparse_access() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(Pos::new(-1_000_000, 5), 'a');
+        grid.set(Pos::new(3, -2), 'b');
+        assert_eq!(grid.set(Pos::new(3, -2), 'c'), Some('b'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(Pos::new(3, -2)), Some(&'c'));
+        assert_eq!(grid.bounds(), Some((Pos::new(-1_000_000, -2), Pos::new(3, 5))));
+
+        assert_eq!(grid.remove(Pos::new(-1_000_000, 5)), Some('a'));
+        assert_eq!(grid.bounds(), Some((Pos::new(3, -2), Pos::new(3, -2))));
+
+        let point = grid.point(Pos::new(2, -1));
+        assert_eq!(point.get(), None);
+        assert_eq!(point.neighbors().count(), 8);
+        assert_eq!(point.occupied_neighbors().map(|(p, v)| (p.pos(), *v)).collect::<Vec<_>>(),
+                   vec![(Pos::new(3, -2), 'c')]);
+        assert_eq!(point.up().right().get(), Some(&'c'));
+    }
+
+    #[test]
+    fn dense_conversion() {
+        let dense = Grid::from_data(vec![true, false, false,
+                                         false, false, true], 3);
+        let sparse = SparseGrid::from_grid_filtered(dense.clone(), |v| *v);
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.to_grid(false), Some((dense.clone(), Pos::ZERO)));
+        assert_eq!(sparse.to_string(), dense.to_string());
+
+        let shifted = sparse.iter().map(|(p, v)| (p + Pos::new(-5, 2), *v)).collect::<SparseGrid<_>>();
+        assert_eq!(shifted.to_grid(false).map(|(_, origin)| origin), Some(Pos::new(-5, 2)));
+        assert_eq!(SparseGrid::from(dense).len(), 6);
+        assert_eq!(SparseGrid::<bool>::new().to_string(), "\n");
+    }
+}
@@ -0,0 +1,200 @@
+//! Addressing of a grid repeated infinitely in every direction
+//!
+//! Unlike a [`Topology`](super::Topology), tiling makes positions unbounded, so it is addressed
+//! through [`TiledPoint`] and signed [`Pos`] values rather than the grid's own points. The only
+//! search supported on the tiled plane is [`Grid::bfs_tiled`]; every other helper on [`Grid`]
+//! sees just the single underlying copy.
+
+use std::collections::hash_map::Entry;
+
+use fnv::FnvHashMap;
+
+use super::bfs::breadth_first;
+use super::{Direction, Grid, GridPoint, Neighborhood, Pos};
+
+impl<T> Grid<T> {
+    /// Split a signed position on the infinitely tiled plane into a cell of this grid and the
+    /// index of the tile containing it
+    ///
+    /// The tile at `Pos::ZERO` is the grid itself.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    pub fn tile_of(&self, pos: Pos) -> ((usize, usize), Pos) {
+        let cell = self.wrap(pos);
+        let tile = Pos::new(pos.x.div_euclid(self.width as isize),
+                            pos.y.div_euclid(self.height as isize));
+        (cell, tile)
+    }
+
+    /// Get a reference to a point on the infinitely tiled plane
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    pub fn tiled_point(&self, pos: Pos) -> TiledPoint<'_, T> {
+        assert!(self.width > 0 && self.height > 0, "Attempted to tile an empty grid");
+        TiledPoint { grid: self, pos }
+    }
+
+    /// Compute distances across the infinitely tiled plane from a set of start points
+    ///
+    /// `can_move(from, to)` is called for each candidate step and should return whether it's
+    /// allowed. Since the plane is unbounded, the search stops at `max_distance`; every reachable
+    /// position within that distance is returned.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty and there are any start points.
+    pub fn bfs_tiled<I, F>(
+        &self,
+        starts: I,
+        neighborhood: Neighborhood,
+        max_distance: u32,
+        mut can_move: F,
+    ) -> FnvHashMap<Pos, u32>
+    where I: IntoIterator<Item=Pos>,
+          F: FnMut(TiledPoint<'_, T>, TiledPoint<'_, T>) -> bool,
+    {
+        let mut distances = FnvHashMap::default();
+
+        breadth_first(
+            starts.into_iter().map(|start| self.tiled_point(start)),
+            neighborhood,
+            max_distance,
+            |cur, dir| Some(cur.step(dir)),
+            |from, to, dist| {
+                let Entry::Vacant(entry) = distances.entry(to.pos()) else { return false; };
+                if from.is_some_and(|from| !(can_move)(from, to)) {
+                    return false;
+                }
+                entry.insert(dist);
+                true
+            },
+        );
+
+        distances
+    }
+}
+
+/// A reference to a point on a grid repeated infinitely in every direction
+///
+/// Navigation never fails; the value at each position is taken from the corresponding cell of
+/// the underlying grid.
+pub struct TiledPoint<'g, T> {
+    grid: &'g Grid<T>,
+    pos: Pos,
+}
+
+impl<T> Clone for TiledPoint<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for TiledPoint<'_, T> {}
+
+impl<'g, T> TiledPoint<'g, T> {
+    /// Get the position of this point on the tiled plane
+    pub fn pos(&self) -> Pos {
+        self.pos
+    }
+
+    /// Get the corresponding point on the underlying grid
+    pub fn base(&self) -> GridPoint<'g, T> {
+        self.grid.point(self.grid.wrap(self.pos))
+    }
+
+    /// Get the index of the tile containing this point
+    pub fn tile(&self) -> Pos {
+        self.grid.tile_of(self.pos).1
+    }
+
+    /// Get the point at a given offset relative to this one
+    pub fn offset(&self, delta: (isize, isize)) -> Self {
+        Self { grid: self.grid, pos: self.pos + Pos::from(delta) }
+    }
+
+    /// Get the adjacent point in a given direction
+    pub fn step(&self, dir: Direction) -> Self {
+        Self { grid: self.grid, pos: self.pos.step(dir) }
+    }
+
+    /// Get the point to the left of this one
+    pub fn left(&self) -> Self {
+        self.step(Direction::Left)
+    }
+
+    /// Get the point to the right of this one
+    pub fn right(&self) -> Self {
+        self.step(Direction::Right)
+    }
+
+    /// Get the point above this one
+    pub fn up(&self) -> Self {
+        self.step(Direction::Up)
+    }
+
+    /// Get the point below this one
+    pub fn down(&self) -> Self {
+        self.step(Direction::Down)
+    }
+
+    /// Iterate over all eight neighboring points
+    pub fn neighbors(&self) -> impl Iterator<Item=TiledPoint<'g, T>> {
+        let point = *self;
+        Direction::ALL.into_iter().map(move |dir| point.step(dir))
+    }
+
+    /// Iterate over the four cardinal neighboring points
+    pub fn neighbors4(&self) -> impl Iterator<Item=TiledPoint<'g, T>> {
+        let point = *self;
+        Direction::CARDINAL.into_iter().map(move |dir| point.step(dir))
+    }
+}
+
+impl<T> std::ops::Deref for TiledPoint<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.grid.get_wrapped(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tile_addressing() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10*y);
+
+        assert_eq!(grid.tile_of(Pos::new(4, 1)), ((1, 1), Pos::new(1, 0)));
+        assert_eq!(grid.tile_of(Pos::new(-1, -3)), ((2, 1), Pos::new(-1, -2)));
+        assert_eq!(*grid.get_wrapped(Pos::new(-4, 5)), 12);
+
+        let point = grid.tiled_point(Pos::ZERO).left().up();
+        assert_eq!(point.pos(), Pos::new(-1, -1));
+        assert_eq!(point.tile(), Pos::new(-1, -1));
+        assert_eq!(point.base().coords(), (2, 1));
+        assert_eq!(*point, 12);
+        // the tile is only two cells high, so both vertical neighbors wrap to the same row
+        assert_eq!(point.neighbors4().filter(|p| **p == 2).count(), 2);
+    }
+
+    #[test]
+    fn tiled_bfs() {
+        // a single rock in a 3x3 tile
+        let grid = Grid::from_fn(3, 3, |x, y| (x, y) != (1, 1));
+        let field = grid.bfs_tiled([Pos::ZERO], Neighborhood::Four, 2, |_, to| *to);
+
+        assert_eq!(field.len(), 12);
+        assert_eq!(field.get(&Pos::new(-2, 0)), Some(&2));
+        assert_eq!(field.get(&Pos::new(1, 1)), None);
+        assert_eq!(field.get(&Pos::new(-2, -2)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to wrap around an empty grid")]
+    fn empty_tiles() {
+        let grid = Grid::<u8>::from_fn(0, 3, |_, _| 0);
+        let _ = grid.tile_of(Pos::ZERO);
+    }
+}
@@ -0,0 +1,315 @@
+//! Borrowed rectangular views into a grid
+
+use super::{Direction, Grid};
+
+/// A borrowed rectangular region of a [`Grid`]
+///
+/// All coordinates used with a view are local to it, so `(0, 0)` is the view's top-left corner.
+pub struct GridView<'g, T> {
+    grid: &'g Grid<T>,
+    origin: (usize, usize),
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    /// Borrow a rectangular region of the grid
+    ///
+    /// # Panics
+    /// Panics if the region extends outside the grid.
+    pub fn view(&self, origin: (usize, usize), size: (usize, usize)) -> GridView<'_, T> {
+        GridView { grid: self, origin: (0, 0), width: self.width, height: self.height }
+            .view(origin, size)
+    }
+
+    /// Iterate over all `width` x `height` windows of the grid
+    ///
+    /// Windows are yielded in row-major order of their top-left corners. If the window is larger
+    /// than the grid, nothing is yielded.
+    ///
+    /// # Panics
+    /// Panics if the window is empty.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item=GridView<'_, T>> {
+        assert!(width > 0 && height > 0, "Windows must not be empty");
+        let nx = (self.width + 1).saturating_sub(width);
+        let ny = (self.height + 1).saturating_sub(height);
+        (0..ny).flat_map(move |y| (0..nx).map(move |x| self.view((x, y), (width, height))))
+    }
+
+    /// Split the grid into non-overlapping `width` x `height` tiles
+    ///
+    /// Tiles are yielded in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the grid's dimensions are not multiples of the tile size.
+    pub fn tiles(&self, width: usize, height: usize) -> impl Iterator<Item=GridView<'_, T>> {
+        assert!(width > 0 && height > 0, "Tiles must not be empty");
+        assert!(self.width.is_multiple_of(width) && self.height.is_multiple_of(height),
+                "Grid is not evenly divisible into tiles");
+
+        let nx = self.width / width;
+        let ny = self.height / height;
+        (0..ny).flat_map(move |y| {
+            (0..nx).map(move |x| self.view((x*width, y*height), (width, height)))
+        })
+    }
+}
+
+impl<'g, T> GridView<'g, T> {
+    /// Get the width of the view
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the view
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the position of the view's top-left corner in the underlying grid
+    pub fn origin(&self) -> (usize, usize) {
+        self.origin
+    }
+
+    /// Borrow a rectangular region of this view
+    ///
+    /// # Panics
+    /// Panics if the region extends outside the view.
+    pub fn view(&self, origin: (usize, usize), size: (usize, usize)) -> GridView<'g, T> {
+        assert!(origin.0 + size.0 <= self.width && origin.1 + size.1 <= self.height,
+                "Attempted to create view extending outside grid");
+        GridView {
+            grid: self.grid,
+            origin: (self.origin.0 + origin.0, self.origin.1 + origin.1),
+            width: size.0,
+            height: size.1,
+        }
+    }
+
+    /// Convert local coordinates to a flat index into the underlying grid's data
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        (self.origin.1 + y) * self.grid.width + self.origin.0 + x
+    }
+
+    /// Get the value at given coordinates
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the view.
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> &'g T {
+        assert!(pos.0 < self.width && pos.1 < self.height,
+                "Attempted to access position ({}, {}) outside view", pos.0, pos.1);
+        &self.grid.data[self.index(pos)]
+    }
+
+    /// Try to get the value at given coordinates
+    #[inline]
+    pub fn try_get(&self, pos: (usize, usize)) -> Option<&'g T> {
+        if !(pos.0 < self.width && pos.1 < self.height) {
+            return None;
+        }
+        Some(&self.grid.data[self.index(pos)])
+    }
+
+    /// Get a reference to a specific point in the view
+    ///
+    /// # Panics
+    /// Panics if the given position is not inside the view.
+    pub fn point(&self, pos: (usize, usize)) -> ViewPoint<'g, T> {
+        assert!(pos.0 < self.width && pos.1 < self.height,
+                "Attempted to access position ({}, {}) outside view", pos.0, pos.1);
+        ViewPoint { view: *self, coords: pos }
+    }
+
+    /// Iterate over each point in the view in row-major order
+    pub fn points(&self) -> impl Iterator<Item=ViewPoint<'g, T>> {
+        let view = *self;
+        (0..self.height).flat_map(move |y| {
+            (0..view.width).map(move |x| ViewPoint { view, coords: (x, y) })
+        })
+    }
+
+    /// Iterate over all cells in row-major order
+    pub fn cells(&self) -> impl Iterator<Item=&'g T> {
+        let view = *self;
+        (0..self.height).flat_map(move |y| {
+            let start = view.index((0, y));
+            view.grid.data[start..start + view.width].iter()
+        })
+    }
+
+    /// Get an iterator over the cells in a given row
+    ///
+    /// # Panics
+    /// Panics if the given row is not inside the view.
+    pub fn row_iter(
+        &self,
+        row: usize,
+    ) -> impl DoubleEndedIterator<Item=ViewPoint<'g, T>> + ExactSizeIterator {
+        assert!(row < self.height, "Attempted to access row outside the view");
+
+        let view = *self;
+        (0..self.width).map(move |col| ViewPoint { view, coords: (col, row) })
+    }
+
+    /// Get an iterator over the cells in a given column
+    ///
+    /// # Panics
+    /// Panics if the given column is not inside the view.
+    pub fn col_iter(&self, col: usize) -> impl DoubleEndedIterator<Item=&'g T> + ExactSizeIterator {
+        assert!(col < self.width, "Attempted to access column outside the view");
+
+        let view = *self;
+        (0..self.height).map(move |row| &view.grid.data[view.index((col, row))])
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the contents of the view into a new grid
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self.get((x, y)).clone())
+    }
+}
+
+/// A reference to a specific point in a [`GridView`]
+///
+/// Navigation is bounded by the edges of the view rather than the underlying grid.
+pub struct ViewPoint<'g, T> {
+    view: GridView<'g, T>,
+    coords: (usize, usize),
+}
+
+impl<T> Clone for ViewPoint<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for ViewPoint<'_, T> {}
+
+impl<'g, T> ViewPoint<'g, T> {
+    /// Get the local coordinates of this point within its view
+    pub fn coords(&self) -> (usize, usize) {
+        self.coords
+    }
+
+    /// Get the coordinates of this point in the underlying grid
+    pub fn grid_coords(&self) -> (usize, usize) {
+        (self.view.origin.0 + self.coords.0, self.view.origin.1 + self.coords.1)
+    }
+
+    /// Get the cell at a given offset relative to this one, if it is inside the view
+    pub fn offset(&self, (dx, dy): (isize, isize)) -> Option<Self> {
+        let x = self.coords.0.checked_add_signed(dx)?;
+        let y = self.coords.1.checked_add_signed(dy)?;
+        if x >= self.view.width || y >= self.view.height {
+            return None;
+        }
+
+        Some(Self { view: self.view, coords: (x, y) })
+    }
+
+    /// Get the adjacent cell in a given direction, if it is inside the view
+    pub fn step(&self, dir: Direction) -> Option<Self> {
+        self.offset(dir.offset())
+    }
+
+    /// Get the cell to the left of this one, if it exists
+    pub fn left(&self) -> Option<Self> {
+        self.step(Direction::Left)
+    }
+
+    /// Get the cell to the right of this one, if it exists
+    pub fn right(&self) -> Option<Self> {
+        self.step(Direction::Right)
+    }
+
+    /// Get the cell above this one, if it exists
+    pub fn up(&self) -> Option<Self> {
+        self.step(Direction::Up)
+    }
+
+    /// Get the cell below this one, if it exists
+    pub fn down(&self) -> Option<Self> {
+        self.step(Direction::Down)
+    }
+
+    /// Iterate over neighboring cells inside the view
+    pub fn neighbors(&self) -> impl Iterator<Item=ViewPoint<'g, T>> {
+        let point = *self;
+        Direction::ALL.into_iter().filter_map(move |dir| point.step(dir))
+    }
+
+    /// Iterate over the cardinal neighbors of this cell inside the view
+    pub fn neighbors4(&self) -> impl Iterator<Item=ViewPoint<'g, T>> {
+        let point = *self;
+        Direction::CARDINAL.into_iter().filter_map(move |dir| point.step(dir))
+    }
+}
+
+impl<T> std::ops::Deref for ViewPoint<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.view.grid.data[self.view.index(self.coords)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn view_access() {
+        let grid = Grid::from_fn(5, 4, |x, y| x + 10*y);
+        let view = grid.view((1, 1), (3, 2));
+
+        assert_eq!(view.width(), 3);
+        assert_eq!(view.height(), 2);
+        assert_eq!(*view.get((0, 0)), 11);
+        assert_eq!(view.try_get((3, 0)), None);
+        assert_eq!(view.cells().cloned().collect::<Vec<_>>(), vec![11, 12, 13, 21, 22, 23]);
+        assert_eq!(view.row_iter(1).rev().map(|p| *p).collect::<Vec<_>>(), vec![23, 22, 21]);
+        assert_eq!(view.col_iter(2).cloned().collect::<Vec<_>>(), vec![13, 23]);
+        assert_eq!(view.points().count(), 6);
+
+        let corner = view.point((0, 0));
+        assert!(corner.left().is_none() && corner.up().is_none());
+        assert_eq!(corner.neighbors().map(|p| *p).collect::<Vec<_>>(), vec![12, 22, 21]);
+        assert_eq!(corner.neighbors4().count(), 2);
+        assert_eq!(view.point((2, 1)).grid_coords(), (3, 2));
+
+        let inner = view.view((1, 0), (2, 2));
+        assert_eq!(inner.origin(), (2, 1));
+        assert_eq!(inner.to_grid(), Grid::from_data(vec![12, 13, 22, 23], 2));
+    }
+
+    #[test]
+    fn windows_and_tiles() {
+        let grid = Grid::from_fn(4, 4, |x, y| x + 4*y);
+
+        let windows = grid.windows(3, 2).map(|v| v.origin()).collect::<Vec<_>>();
+        assert_eq!(windows, vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+
+        let empty = grid.view((1, 1), (0, 2)).to_grid();
+        assert_eq!((empty.width(), empty.height()), (0, 2));
+
+        let tiles = grid.tiles(2, 2).map(|v| *v.get((1, 1))).collect::<Vec<_>>();
+        assert_eq!(tiles, vec![5, 7, 13, 15]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Windows must not be empty")]
+    fn empty_windows() {
+        let grid = Grid::from_fn(4, 4, |x, y| x + 4*y);
+        let _ = grid.windows(0, 2).count();
+    }
+}
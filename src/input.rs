@@ -0,0 +1,194 @@
+//! Raw input normalization
+//!
+//! Every input passes through [`normalize`] before it reaches a problem's loader, so loaders can
+//! assume UTF-8 text with LF line endings, no trailing whitespace on any line, and exactly one
+//! final newline.
+
+use anyhow::{Context, Result};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Summary of the fixes that were needed to normalize an input
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Whether a UTF-8 byte-order mark was stripped
+    pub bom: bool,
+
+    /// Number of CRLF line endings converted to LF
+    pub crlf: usize,
+
+    /// Number of bare CR line endings converted to LF
+    pub bare_cr: usize,
+
+    /// Number of lines which had trailing whitespace removed
+    pub trailing_ws: usize,
+
+    /// Whether the final line was missing its newline
+    pub missing_newline: bool,
+
+    /// Number of blank lines removed from the end of the input
+    pub trailing_blank: usize,
+}
+
+impl Report {
+    /// Check whether the input was already in normal form
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut fixes = Vec::new();
+        if self.bom {
+            fixes.push("stripped byte-order mark".to_owned());
+        }
+        if self.crlf > 0 {
+            fixes.push(format!("converted {} CRLF line endings", self.crlf));
+        }
+        if self.bare_cr > 0 {
+            fixes.push(format!("converted {} bare CR line endings", self.bare_cr));
+        }
+        if self.trailing_ws > 0 {
+            fixes.push(format!("trimmed trailing whitespace on {} lines", self.trailing_ws));
+        }
+        if self.missing_newline {
+            fixes.push("added missing final newline".to_owned());
+        }
+        if self.trailing_blank > 0 {
+            fixes.push(format!("removed {} trailing blank lines", self.trailing_blank));
+        }
+
+        if fixes.is_empty() {
+            write!(f, "no changes")
+        } else {
+            write!(f, "{}", fixes.join(", "))
+        }
+    }
+}
+
+/// Normalize raw input bytes
+///
+/// Returns the cleaned text along with a report of which fixes were applied.
+pub fn normalize(raw: &[u8]) -> Result<(String, Report)> {
+    let mut report = Report::default();
+
+    let raw = match raw.strip_prefix(BOM) {
+        Some(rest) => {
+            report.bom = true;
+            rest
+        }
+        None => raw,
+    };
+    let text = std::str::from_utf8(raw).context("Input is not valid UTF-8")?;
+
+    let mut lines = Vec::new();
+    let mut unterminated = false;
+    for piece in text.split_inclusive('\n') {
+        let mut content = match piece.strip_suffix('\n') {
+            Some(c) => match c.strip_suffix('\r') {
+                Some(c) => {
+                    report.crlf += 1;
+                    c
+                }
+                None => c,
+            },
+            None => match piece.strip_suffix('\r') {
+                Some(c) => {
+                    report.bare_cr += 1;
+                    c
+                }
+                None => {
+                    unterminated = true;
+                    piece
+                }
+            },
+        };
+
+        // anything left over is a line separated by bare CRs
+        while let Some((line, rest)) = content.split_once('\r') {
+            report.bare_cr += 1;
+            lines.push(line);
+            content = rest;
+        }
+        lines.push(content);
+    }
+
+    for line in lines.iter_mut() {
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            report.trailing_ws += 1;
+            *line = trimmed;
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+        report.trailing_blank += 1;
+    }
+
+    // an unterminated final line only matters if it survived the trimming above
+    report.missing_newline = unterminated && report.trailing_blank == 0;
+
+    let mut out = String::with_capacity(text.len() + 1);
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    Ok((out, report))
+}
+
+/// Get the path of the default input file for a given (1-based) problem number
+pub fn default_path(problem: usize) -> PathBuf {
+    std::path::Path::new("inputs").join(format!("{:02}", problem))
+}
+
+/// Read an entire input and normalize it
+///
+/// Returns a reader over the normalized text, suitable for passing to a problem's loader.
+pub fn read_normalized(input: &mut dyn std::io::Read) -> Result<(Cursor<String>, Report)> {
+    let mut raw = Vec::new();
+    input.read_to_end(&mut raw)?;
+
+    let (text, report) = normalize(&raw)?;
+    Ok((Cursor::new(text), report))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clean_input_unchanged() {
+        let (text, report) = normalize(b"abc\n\ndef\n").unwrap();
+        assert_eq!(text, "abc\n\ndef\n");
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn line_endings() {
+        let (text, report) = normalize(b"\xEF\xBB\xBFa\r\nb\rc\r\nd").unwrap();
+        assert_eq!(text, "a\nb\nc\nd\n");
+        assert!(report.bom);
+        assert_eq!(report.crlf, 2);
+        assert_eq!(report.bare_cr, 1);
+        assert!(report.missing_newline);
+    }
+
+    #[test]
+    fn whitespace() {
+        let (text, report) = normalize(b"a  \n \nb\t\n\n  \n").unwrap();
+        assert_eq!(text, "a\n\nb\n");
+        assert_eq!(report.trailing_ws, 4);
+        assert_eq!(report.trailing_blank, 2);
+        assert!(!report.missing_newline);
+
+        let (text, report) = normalize(b"a\n \t").unwrap();
+        assert_eq!(text, "a\n");
+        assert_eq!(report.trailing_blank, 1);
+        assert!(!report.missing_newline);
+    }
+}
@@ -0,0 +1,146 @@
+//! Advent of Code 2023 solutions
+//!
+//! The runner binary and benchmarks both drive problems through the [`PROBLEMS`] registry.
+
+use anyhow::Result;
+use std::sync::Arc;
+
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod scaling;
+pub mod search;
+pub mod timing;
+pub mod util;
+
+macro_rules! problem {
+    (@generate) => { None };
+    (@generate $gen:path) => { Some($gen) };
+    (@dump $input:ty) => {
+        |input| {
+            #[allow(unused_imports)]
+            use crate::dump::{DumpDebug, DumpFallback};
+
+            let input = input.downcast_ref::<$input>().expect("Inconsistent data types");
+            (&crate::dump::Dump(input)).dump()
+        }
+    };
+    ($load:path => $input:ty => () $(; $gen:path)?) => {
+        type Input = $input;
+
+        pub(crate) const PROBLEM: crate::Problem  = crate::Problem {
+            load_input: |d| $load(d).map(|x: $input| -> std::sync::Arc<dyn std::any::Any> {
+                std::sync::Arc::new(x)
+            }),
+            solve1: None,
+            solve2: None,
+            dump_input: problem!(@dump Input),
+            generate: problem!(@generate $($gen)?),
+        };
+    };
+    ($load:path => $input:ty => ($solve1:ident) $(; $gen:path)?) => {
+        type Input = $input;
+
+        pub(crate) const PROBLEM: crate::Problem = crate::Problem {
+            load_input: |d| $load(d).map(|x: Input| -> std::sync::Arc<dyn std::any::Any> {
+                std::sync::Arc::new(x)
+            }),
+            solve1: Some(|input| {
+                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
+                ($solve1)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
+            }),
+            solve2: None,
+            dump_input: problem!(@dump Input),
+            generate: problem!(@generate $($gen)?),
+        };
+    };
+    ($load:path => $input:ty => ($solve1:ident, $solve2:ident) $(; $gen:path)?) => {
+        type Input = $input;
+
+        pub(crate) const PROBLEM: crate::Problem  = crate::Problem {
+            load_input: |d| $load(d).map(|x: Input| -> std::sync::Arc<dyn std::any::Any> {
+                std::sync::Arc::new(x)
+            }),
+            solve1: Some(|input| {
+                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
+                ($solve1)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
+            }),
+            solve2: Some(|input| {
+                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
+                ($solve2)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
+            }),
+            dump_input: problem!(@dump Input),
+            generate: problem!(@generate $($gen)?),
+        };
+    };
+}
+
+macro_rules! problems {
+    {$($mod_ident:ident)*} => {
+        $(
+            mod $mod_ident ;
+        )*
+        pub const PROBLEMS: &[Problem] = &[$($mod_ident::PROBLEM),*];
+    };
+}
+
+/// Autoref-based dispatch letting `problem!` use `Debug` only when the input type implements it
+///
+/// Method lookup on `&Dump<T>` finds `DumpDebug` first when `T: Debug`, and otherwise falls back to
+/// `DumpFallback` after an extra autoref.
+mod dump {
+    pub struct Dump<'a, T>(pub &'a T);
+
+    pub trait DumpDebug {
+        fn dump(&self) -> Option<String>;
+    }
+
+    impl<T: std::fmt::Debug> DumpDebug for Dump<'_, T> {
+        fn dump(&self) -> Option<String> {
+            Some(format!("{:#?}", self.0))
+        }
+    }
+
+    #[allow(dead_code)] // unused while every input type implements Debug
+    pub trait DumpFallback {
+        fn dump(&self) -> Option<String>;
+    }
+
+    impl<T> DumpFallback for &Dump<'_, T> {
+        fn dump(&self) -> Option<String> {
+            None
+        }
+    }
+}
+
+pub type Solver = fn(Arc<dyn std::any::Any>) -> Result<Box<dyn std::fmt::Display + Send>>;
+
+pub struct Problem {
+    pub load_input: fn(&mut dyn std::io::BufRead) -> Result<std::sync::Arc<dyn std::any::Any>>,
+    pub solve1: Option<Solver>,
+    pub solve2: Option<Solver>,
+
+    /// Pretty-print a loaded input, if its type implements `Debug`
+    pub dump_input: fn(&dyn std::any::Any) -> Option<String>,
+
+    /// Generator for synthetic inputs of a given size, used for scaling measurements
+    pub generate: Option<fn(usize) -> String>,
+}
+
+problems! {
+    p01 p02 p03 p04 p05
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    #[allow(clippy::needless_borrow)] // the explicit borrow is what drives the dispatch
+    fn dump_dispatch() {
+        use crate::dump::{Dump, DumpDebug, DumpFallback};
+
+        struct Opaque;
+
+        assert_eq!((&Dump(&vec![1, 2])).dump().as_deref(), Some("[\n    1,\n    2,\n]"));
+        assert_eq!((&Dump(&Opaque)).dump(), None);
+    }
+}
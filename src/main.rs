@@ -1,73 +1,9 @@
-use anyhow::Result;
 use std::sync::Arc;
 
-mod grid;
-mod util;
+use aoc2023::{input, scaling, timing, PROBLEMS};
 
 const SAMPLES: usize = 2000;
 
-macro_rules! problem {
-    ($load:path => $input:ty => ()) => {
-        type Input = $input;
-
-        pub(crate) const PROBLEM: crate::Problem  = crate::Problem {
-            load_input: |d| $load(d).map(|x: $input| -> std::sync::Arc<dyn std::any::Any> {
-                std::sync::Arc::new(x)
-            }),
-            solve1: None,
-            solve2: None,
-        };
-    };
-    ($load:path => $input:ty => ($solve1:ident)) => {
-        type Input = $input;
-
-        pub(crate) const PROBLEM: crate::Problem = crate::Problem {
-            load_input: |d| $load(d).map(|x: Input| -> std::sync::Arc<dyn std::any::Any> {
-                std::sync::Arc::new(x)
-            }),
-            solve1: Some(|input| {
-                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
-                ($solve1)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
-            }),
-            solve2: None,
-        };
-    };
-    ($load:path => $input:ty => ($solve1:ident, $solve2:ident)) => {
-        type Input = $input;
-
-        pub(crate) const PROBLEM: crate::Problem  = crate::Problem {
-            load_input: |d| $load(d).map(|x: Input| -> std::sync::Arc<dyn std::any::Any> {
-                std::sync::Arc::new(x)
-            }),
-            solve1: Some(|input| {
-                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
-                ($solve1)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
-            }),
-            solve2: Some(|input| {
-                let input = input.downcast_ref::<Input>().expect("Inconsistent data types");
-                ($solve2)(input).map(|x: _| -> Box<dyn std::fmt::Display + Send> {Box::new(x)})
-            }),
-        };
-    };
-}
-
-macro_rules! problems {
-    {$($mod_ident:ident)*} => {
-        $(
-            mod $mod_ident ;
-        )*
-        const PROBLEMS: &[Problem] = &[$($mod_ident::PROBLEM),*];
-    };
-}
-
-type Solver = fn(Arc<dyn std::any::Any>) -> Result<Box<dyn std::fmt::Display + Send>>;
-
-struct Problem {
-    load_input: fn(&mut dyn std::io::BufRead) -> Result<std::sync::Arc<dyn std::any::Any>>,
-    solve1: Option<Solver>,
-    solve2: Option<Solver>,
-}
-
 fn main() {
     let mut args = std::env::args().skip(1);
     if let Some(prob) = args.next() {
@@ -90,10 +26,21 @@ fn main() {
             std::process::exit(1);
         };
 
+        if std::env::var_os("SCALING").is_some() {
+            match scaling::measure(problem) {
+                Ok(points) => scaling::print_report(&points),
+                Err(e) => {
+                    eprintln!("{:02}: Scaling measurement failed: {}", prob_number, e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+
         // open input
         let mut input: Box<dyn std::io::BufRead> = match args.next().as_deref() {
             None => {
-                let input = std::path::Path::new("inputs").join(format!("{:02}", prob_number));
+                let input = input::default_path(prob_number);
                 let input = match std::fs::File::open(input) {
                     Ok(x) => x,
                     Err(e) => {
@@ -120,6 +67,19 @@ fn main() {
             },
         };
 
+        let mut input = match input::read_normalized(&mut input) {
+            Ok((text, report)) => {
+                if !report.is_clean() {
+                    eprintln!("{:02}: warning: input was normalized: {}", prob_number, report);
+                }
+                text
+            }
+            Err(e) => {
+                eprintln!("{:02}: Failed to read input: {}", prob_number, e);
+                std::process::exit(1);
+            }
+        };
+
         let input = match (problem.load_input)(&mut input) {
             Ok(x) => x,
             Err(e) => {
@@ -128,6 +88,22 @@ fn main() {
             }
         };
 
+        let verbose = std::env::var_os("VERBOSE").is_some();
+        timing::enable(verbose);
+        timing::take();
+
+        if std::env::var_os("DUMP").is_some() {
+            match (problem.dump_input)(&*input) {
+                Some(dump) => println!("{}", dump),
+                None => {
+                    eprintln!("{:02}: Input type does not implement Debug, so it can't be dumped",
+                              prob_number);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+
         if let Some(p1) = problem.solve1 {
             match (p1)(Arc::clone(&input)) {
                 Ok(x) => {
@@ -137,6 +113,11 @@ fn main() {
                     eprintln!("{:02}: Part 1 failed: {}", prob_number, e);
                 }
             }
+            let spans = timing::take();
+            if !spans.is_empty() {
+                println!("{:02}p1:", prob_number);
+                print!("{}", spans);
+            }
         }
         if let Some(p2) = problem.solve2 {
             match (p2)(input) {
@@ -147,16 +128,23 @@ fn main() {
                     eprintln!("{:02}: Part 2 failed: {}", prob_number, e);
                 }
             }
+            let spans = timing::take();
+            if !spans.is_empty() {
+                println!("{:02}p2:", prob_number);
+                print!("{}", spans);
+            }
         }
     } else {
         let do_bench = std::env::var_os("BENCHMARK").is_some();
+        let verbose = std::env::var_os("VERBOSE").is_some();
         let mut results = Vec::new();
+        timing::enable(verbose && !do_bench);
 
         let begin = std::time::Instant::now();
         for (idx, prob) in PROBLEMS.iter().enumerate() {
             let p_num = idx + 1;
 
-            let input = std::path::Path::new("inputs").join(format!("{:02}", p_num));
+            let input = input::default_path(p_num);
             let input = match std::fs::File::open(input) {
                 Ok(x) => x,
                 Err(e) => {
@@ -164,7 +152,18 @@ fn main() {
                     continue;
                 }
             };
-            let mut input = std::io::BufReader::new(input);
+            let mut input = match input::read_normalized(&mut std::io::BufReader::new(input)) {
+                Ok((text, report)) => {
+                    if !report.is_clean() {
+                        eprintln!("{:02}: warning: input was normalized: {}", p_num, report);
+                    }
+                    text
+                }
+                Err(e) => {
+                    eprintln!("{:02}: Failed to read input: {}", p_num, e);
+                    continue;
+                }
+            };
 
             let input = match (prob.load_input)(&mut input) {
                 Ok(x) => x,
@@ -175,6 +174,7 @@ fn main() {
             };
 
             let mut samples = Vec::new();
+            timing::take(); // discard anything recorded while loading
             if let Some(p1) = prob.solve1 {
                 if do_bench {
                     for _ in 0..SAMPLES {
@@ -183,6 +183,12 @@ fn main() {
                         let dur = start.elapsed();
                         samples.push(dur);
                     }
+
+                    // Collect spans from one extra run so their bookkeeping stays out of the
+                    // averages above
+                    timing::enable(true);
+                    let _ = std::hint::black_box((p1)(Arc::clone(&input)));
+                    timing::enable(false);
                 } else {
                     if let Err(e) = (p1)(Arc::clone(&input)).map(std::hint::black_box) {
                         eprintln!("{:02}: Part 1 failed: {}", p_num, e);
@@ -195,6 +201,16 @@ fn main() {
             } else {
                 None
             };
+            let spans1 = if do_bench {
+                timing::take()
+            } else {
+                let spans = timing::take();
+                if !spans.is_empty() {
+                    println!("{:02}p1:", p_num);
+                    print!("{}", spans);
+                }
+                spans
+            };
 
             if let Some(p2) = prob.solve2 {
                 if do_bench {
@@ -204,6 +220,12 @@ fn main() {
                         let dur = start.elapsed();
                         samples.push(dur);
                     }
+
+                    // Collect spans from one extra run so their bookkeeping stays out of the
+                    // averages above
+                    timing::enable(true);
+                    let _ = std::hint::black_box((p2)(Arc::clone(&input)));
+                    timing::enable(false);
                 } else {
                     if let Err(e) = (p2)(input).map(std::hint::black_box) {
                         eprintln!("{:02}: Part 2 failed: {}", p_num, e);
@@ -216,17 +238,27 @@ fn main() {
             } else {
                 None
             };
+            let spans2 = if do_bench {
+                timing::take()
+            } else {
+                let spans = timing::take();
+                if !spans.is_empty() {
+                    println!("{:02}p2:", p_num);
+                    print!("{}", spans);
+                }
+                spans
+            };
 
             if do_bench {
-                results.push((avg1, avg2));
+                results.push((p_num, avg1, avg2, spans1, spans2));
             }
         }
         let end = std::time::Instant::now();
         let dur = end.duration_since(begin);
 
         if do_bench {
-            for (idx, (avg1, avg2)) in results.into_iter().enumerate() {
-                print!("{:02}: ", idx+1);
+            for (p_num, avg1, avg2, spans1, spans2) in results {
+                print!("{:02}: ", p_num);
                 if let Some(avg1) = avg1 {
                     print!("p1={:<12?}  ", avg1);
                 }
@@ -234,13 +266,18 @@ fn main() {
                     print!("p2={:<12?}", avg2);
                 }
                 println!();
+
+                if !spans1.is_empty() {
+                    println!("  p1:");
+                    print!("{}", spans1);
+                }
+                if !spans2.is_empty() {
+                    println!("  p2:");
+                    print!("{}", spans2);
+                }
             }
         } else {
             println!("Solved {} problems in {} ms", PROBLEMS.len(), dur.as_millis());
         }
     }
 }
-
-problems! {
-    p01 p02 p03 p04 p05
-}
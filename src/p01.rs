@@ -3,6 +3,7 @@ use anyhow::{anyhow, Result};
 use std::iter::DoubleEndedIterator;
 use std::str::FromStr;
 
+#[derive(Debug)]
 struct Line(String);
 
 impl FromStr for Line {
@@ -135,9 +136,9 @@ impl Line {
     }
 }
 
-fn calibration<I: DoubleEndedIterator<Item=u8> + Clone>(iter: I) -> Option<u64> {
+fn calibration<I: DoubleEndedIterator<Item=u8> + Clone>(mut iter: I) -> Option<u64> {
     let d0 = iter.clone().next()? as u64;
-    let d1 = iter.rev().next()? as u64;
+    let d1 = iter.next_back()? as u64;
 
     Some((d0 * 10) + d1)
 }
@@ -154,4 +155,28 @@ fn solve2(lines: &Input) -> Result<u64> {
          .sum()
 }
 
-problem!(crate::util::load_lines => Vec<Line> => (solve1, solve2));
+/// Generate `n` random calibration lines, each containing at least one digit
+fn generate(n: usize) -> String {
+    use std::fmt::Write;
+
+    const WORDS: &[&str] = &["one", "two", "three", "four", "five", "six", "seven", "eight",
+                             "nine", "a", "x", "q", "z", "on", "tw", "eig"];
+
+    let mut rng = crate::util::Rng::new(n as u64);
+    let mut out = String::new();
+    for _ in 0..n {
+        write!(out, "{}", rng.below(10)).unwrap();
+        for _ in 0..rng.below(12) {
+            if rng.below(4) == 0 {
+                write!(out, "{}", rng.below(10)).unwrap();
+            } else {
+                let word: &&str = rng.pick(WORDS);
+                out.push_str(word);
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+problem!(crate::util::load_lines => Vec<Line> => (solve1, solve2); generate);
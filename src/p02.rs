@@ -57,14 +57,40 @@ impl Game {
 fn solve1(lines: &Input) -> Result<u64> {
     Ok(lines.iter()
       .filter(|g| g.plausible_for_start([12, 13, 14]))
-      .map(|g| g.id)
-      .sum::<u32>() as u64)
+      .map(|g| g.id as u64)
+      .sum())
 }
 
 fn solve2(lines: &Input) -> Result<u64> {
     Ok(lines.iter()
-      .map(|g| g.min_cubes().into_iter().product::<u32>())
-      .sum::<u32>() as u64)
+      .map(|g| g.min_cubes().into_iter().product::<u32>() as u64)
+      .sum())
 }
 
-problem!(load_input => Vec<Game> => (solve1, solve2));
+/// Generate `n` random games
+fn generate(n: usize) -> String {
+    use std::fmt::Write;
+
+    const COLORS: &[&str] = &["red", "green", "blue"];
+
+    let mut rng = crate::util::Rng::new(n as u64);
+    let mut out = String::new();
+    for id in 1..=n {
+        write!(out, "Game {}:", id).unwrap();
+        for turn in 0..1 + rng.below(6) {
+            if turn > 0 {
+                out.push(';');
+            }
+            for draw in 0..1 + rng.below(3) {
+                if draw > 0 {
+                    out.push(',');
+                }
+                write!(out, " {} {}", 1 + rng.below(20), rng.pick(COLORS)).unwrap();
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+problem!(load_input => Vec<Game> => (solve1, solve2); generate);
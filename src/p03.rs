@@ -1,6 +1,6 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use std::collections::HashSet;
-use crate::grid::Grid;
+use crate::grid::{Axis, Grid};
 
 #[derive(Copy, Clone, Debug)]
 enum Cell {
@@ -32,43 +32,14 @@ struct NumberMap {
 
 impl NumberMap {
     fn from_grid(grid: &Input) -> Self {
-        enum State {
-            Idle,
-            Number {
-                accum: u32,
-                ident: usize,
-            },
-        }
-
-        // find contiguous numbers by running a state machine over each line
-        let mut numbers = Vec::new();
-        let mut number_ids = Grid::filled_like(&grid, None);
-
-        for row_idx in 0..grid.height() {
-            let mut state = State::Idle;
-            for cell in grid.row_iter(row_idx) {
-                match (&mut state, *cell) {
-                    (State::Idle, Cell::Digit(n)) => {
-                        state = State::Number { accum: n as u32, ident: numbers.len() };
-                        number_ids.set(cell.coords(), Some(numbers.len()));
-                    }
-                    (State::Idle, _) => {}
-                    (State::Number { accum, ident }, Cell::Digit(n)) => {
-                        *accum = 10*(*accum) + (n as u32);
-                        number_ids.set(cell.coords(), Some(*ident));
-                    }
-                    (State::Number { accum, ident }, _) => {
-                        assert_eq!(numbers.len(), *ident);
-                        numbers.push(*accum);
-                        state = State::Idle;
-                    }
-                }
-            }
-
-            if let State::Number { accum, ident: _ } = state {
-                numbers.push(accum);
-            }
-        }
+        let (runs, number_ids) = grid.runs_labeled(Axis::Horizontal,
+                                                   |c| matches!(c, Cell::Digit(_)));
+        let numbers = runs.iter()
+                     .map(|run| run.cells().fold(0, |acc, c| match *c {
+                         Cell::Digit(n) => 10*acc + (n as u32),
+                         _ => unreachable!("Run contains non-digit cell"),
+                     }))
+                     .collect();
 
         NumberMap { numbers, number_ids }
     }
@@ -85,8 +56,8 @@ fn solve1(grid: &Input) -> Result<u64> {
     }
 
     Ok(used_ids.into_iter()
-               .map(|id| map.numbers[id])
-               .sum::<u32>() as u64)
+               .map(|id| map.numbers[id] as u64)
+               .sum())
 }
 
 fn solve2(grid: &Input) -> Result<u64> {
@@ -119,9 +90,29 @@ fn solve2(grid: &Input) -> Result<u64> {
                if n < 2 {
                    return None;
                }
-               Some(vals[0] * vals[1])
+               Some(vals[0] as u64 * vals[1] as u64)
            })
-           .sum::<u32>() as u64)
+           .sum())
+}
+
+/// Generate a random square schematic with roughly `n` cells
+fn generate(n: usize) -> String {
+    const SYMBOLS: &[char] = &['*', '*', '#', '+', '$', '/'];
+
+    let side = (n as f64).sqrt().ceil() as usize;
+    let mut rng = crate::util::Rng::new(n as u64);
+    let mut out = String::with_capacity(side * (side + 1));
+    for _ in 0..side {
+        for _ in 0..side {
+            out.push(match rng.below(10) {
+                0..=5 => '.',
+                6..=8 => (b'0' + rng.below(10) as u8) as char,
+                _     => *rng.pick(SYMBOLS),
+            });
+        }
+        out.push('\n');
+    }
+    out
 }
 
-problem!(crate::util::load_grid => Grid<Cell> => (solve1, solve2));
+problem!(crate::util::load_grid => Grid<Cell> => (solve1, solve2); generate);
@@ -3,6 +3,7 @@ use anyhow::Result;
 /// A single scratch card
 ///
 /// Numbers are always two digits at most, so they're represented as bitsets here for efficiency.
+#[derive(Debug)]
 struct Card {
     winning: u128,
     numbers: u128,
@@ -44,7 +45,7 @@ fn solve2(input: &Input) -> Result<u64> {
     let mut expiring = vec![0; input.len()]; // number of cards expiring at each point
 
     let mut total_cards = 0; // total number of cards processed
-    for (card_idx, card) in input.into_iter().enumerate() {
+    for (card_idx, card) in input.iter().enumerate() {
         dupes -= expiring[card_idx];
         total_cards += dupes;
 
@@ -64,4 +65,32 @@ fn solve2(input: &Input) -> Result<u64> {
     Ok(total_cards)
 }
 
-problem!(load_input => Vec<Card> => (solve1, solve2));
+/// Generate `n` random cards
+///
+/// Each card has at most one match, since otherwise the number of copies in part 2 grows
+/// exponentially with the number of cards.
+fn generate(n: usize) -> String {
+    use std::fmt::Write;
+
+    let mut rng = crate::util::Rng::new(n as u64);
+    let mut out = String::new();
+    for id in 1..=n {
+        // winning numbers are drawn from 1..50 and the rest from 50..100, except for one
+        // optional match
+        write!(out, "Card {:>3}:", id).unwrap();
+        let winning = (0..10).map(|_| 1 + rng.below(49)).collect::<Vec<_>>();
+        for w in &winning {
+            write!(out, " {:>2}", w).unwrap();
+        }
+        out.push_str(" |");
+        let matched = rng.below(2) == 0;
+        for i in 0..25 {
+            let num = if matched && i == 0 { winning[0] } else { 50 + rng.below(50) };
+            write!(out, " {:>2}", num).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+problem!(load_input => Vec<Card> => (solve1, solve2); generate);
@@ -1,4 +1,5 @@
 use anyhow::{Result, Context};
+use crate::timing;
 
 macro_rules! newtypes {
     { $($i:ident),* } => {
@@ -57,6 +58,7 @@ trait Mappable: std::fmt::Debug + PartialEq + Eq + PartialOrd + Ord + Copy + Fro
 
 newtypes! { Seed, Soil, Fertilizer, Water, Light, Temp, Humidity, Location }
 
+#[derive(Debug)]
 struct MapEntry<S, D> {
     /// Source start value
     src: S,
@@ -75,11 +77,13 @@ impl<S: Mappable, D: Mappable> MapEntry<S, D> {
     }
 }
 
+#[derive(Debug)]
 struct Map<S: Mappable, D: Mappable> {
     /// Source-ordered list
     ranges: Vec<MapEntry<S, D>>,
 }
 
+#[derive(Debug)]
 struct Problem {
     seeds: Vec<Seed>,
     seed_soil: Map<Seed, Soil>,
@@ -247,19 +251,57 @@ fn solve1(input: &Problem) -> Result<u64> {
 fn solve2(input: &Problem) -> Result<u64> {
     anyhow::ensure!(input.seeds.len() % 2 == 0);
 
+    macro_rules! stage {
+        ($map:ident) => {
+            |x| {
+                let _t = timing::span(stringify!($map));
+                input.$map.map_range(x).collect::<Vec<_>>()
+            }
+        };
+    }
+
     let out = input.seeds.chunks_exact(2)
              .map(|chunk| (chunk[0], chunk[1].0))
-             .flat_map(|x| input.seed_soil.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.soil_fert.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.fert_water.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.water_light.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.light_temp.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.temp_humid.map_range(x).collect::<Vec<_>>())
-             .flat_map(|x| input.humid_loc.map_range(x).collect::<Vec<_>>())
+             .flat_map(stage!(seed_soil))
+             .flat_map(stage!(soil_fert))
+             .flat_map(stage!(fert_water))
+             .flat_map(stage!(water_light))
+             .flat_map(stage!(light_temp))
+             .flat_map(stage!(temp_humid))
+             .flat_map(stage!(humid_loc))
              .map(|span| span.0)
              .min();
 
     out.map(|x| x.into()).context("No ranges")
 }
 
-problem!(load_input => Problem => (solve1, solve2));
+/// Generate an almanac with `n` seed ranges and `n` ranges in each map
+fn generate(n: usize) -> String {
+    use std::fmt::Write;
+
+    const MAPS: &[&str] = &["seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water",
+                            "water-to-light", "light-to-temperature", "temperature-to-humidity",
+                            "humidity-to-location"];
+    const DOMAIN: u64 = 1 << 40;
+
+    let mut rng = crate::util::Rng::new(n as u64);
+    let mut out = String::from("seeds:");
+    for _ in 0..n {
+        write!(out, " {} {}", rng.below(DOMAIN), 1 + rng.below(DOMAIN / n as u64)).unwrap();
+    }
+    out.push('\n');
+
+    // split the domain into `n` slots and place one non-overlapping range in each
+    let slot = DOMAIN / n as u64;
+    for name in MAPS {
+        write!(out, "\n{} map:\n", name).unwrap();
+        for i in 0..n as u64 {
+            let len = 1 + rng.below(slot);
+            let src = i * slot + rng.below(slot - len + 1);
+            writeln!(out, "{} {} {}", rng.below(DOMAIN), src, len).unwrap();
+        }
+    }
+    out
+}
+
+problem!(load_input => Problem => (solve1, solve2); generate);
@@ -0,0 +1,143 @@
+//! Empirical complexity measurement on generated inputs
+//!
+//! Each problem can provide a generator producing a synthetic input for a size parameter `n`.
+//! Scaling mode runs the solvers on inputs of doubling size and fits a power law to the timings,
+//! so the reported exponent approximates the complexity with respect to the input length.
+
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{Problem, Solver};
+
+/// Smallest size parameter to try
+const MIN_SIZE: usize = 16;
+
+/// Largest size parameter to try
+const MAX_SIZE: usize = 1 << 20;
+
+/// Minimum total time to spend running a part at each size
+const TIME_PER_POINT: Duration = Duration::from_millis(100);
+
+/// Stop growing the input once a single run of any part takes longer than this
+const MAX_RUN_TIME: Duration = Duration::from_secs(1);
+
+/// Timings for a single input size
+#[derive(Clone, Debug)]
+pub struct Point {
+    /// Size parameter passed to the generator
+    pub size: usize,
+
+    /// Length of the generated input in bytes
+    pub bytes: usize,
+
+    /// Mean time to run part 1
+    pub p1: Option<Duration>,
+
+    /// Mean time to run part 2
+    pub p2: Option<Duration>,
+}
+
+/// Measure a problem's solvers over a series of progressively larger generated inputs
+pub fn measure(problem: &Problem) -> Result<Vec<Point>> {
+    let generate = problem.generate.ok_or_else(|| anyhow!("Problem has no input generator"))?;
+
+    let mut points = Vec::new();
+    let mut size = MIN_SIZE;
+    while size <= MAX_SIZE {
+        let text = (generate)(size);
+        let input = (problem.load_input)(&mut text.as_bytes())
+                   .map_err(|e| anyhow!("Failed to load generated input (n={}): {}", size, e))?;
+
+        let p1 = problem.solve1.map(|p| time_part(p, &input)).transpose()?;
+        let p2 = problem.solve2.map(|p| time_part(p, &input)).transpose()?;
+        points.push(Point { size, bytes: text.len(), p1, p2 });
+
+        if p1.into_iter().chain(p2).any(|t| t > MAX_RUN_TIME) {
+            break;
+        }
+        size *= 2;
+    }
+
+    Ok(points)
+}
+
+/// Time a single part, returning the mean duration of a run
+fn time_part(solver: Solver, input: &Arc<dyn std::any::Any>) -> Result<Duration> {
+    let mut runs = 0;
+    let begin = Instant::now();
+    loop {
+        std::hint::black_box((solver)(Arc::clone(input)))?;
+        runs += 1;
+
+        let elapsed = begin.elapsed();
+        if elapsed >= TIME_PER_POINT || elapsed >= MAX_RUN_TIME {
+            return Ok(elapsed / runs);
+        }
+    }
+}
+
+/// Fit `y = c * x^k` to a set of points by least squares in log-log space, returning `k`
+///
+/// Points with non-positive coordinates are ignored. Returns `None` if fewer than two distinct
+/// `x` values remain.
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs = points.iter()
+              .filter(|(x, y)| *x > 0. && *y > 0.)
+              .map(|(x, y)| (x.ln(), y.ln()))
+              .collect::<Vec<_>>();
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / n;
+    let cov = logs.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>();
+    let var = logs.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+
+    if var == 0. {
+        None
+    } else {
+        Some(cov / var)
+    }
+}
+
+/// Print a table of timings against input size, followed by the fitted growth exponents
+pub fn print_report(points: &[Point]) {
+    println!("{:>10} {:>12} {:>14} {:>14}", "n", "bytes", "p1", "p2");
+    for pt in points {
+        let fmt = |t: Option<Duration>| t.map(|t| format!("{:?}", t)).unwrap_or_default();
+        println!("{:>10} {:>12} {:>14} {:>14}", pt.size, pt.bytes, fmt(pt.p1), fmt(pt.p2));
+    }
+
+    let fit = |part: fn(&Point) -> Option<Duration>| {
+        let xy = points.iter()
+                .filter_map(|pt| Some((pt.bytes as f64, part(pt)?.as_secs_f64())))
+                .collect::<Vec<_>>();
+        fit_exponent(&xy)
+    };
+    if let Some(k) = fit(|pt| pt.p1) {
+        println!("p1: time ~ bytes^{:.2}", k);
+    }
+    if let Some(k) = fit(|pt| pt.p2) {
+        println!("p2: time ~ bytes^{:.2}", k);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exponent_fit() {
+        let linear = (1..10).map(|x| (x as f64, 3. * x as f64)).collect::<Vec<_>>();
+        assert!((fit_exponent(&linear).unwrap() - 1.).abs() < 1e-9);
+
+        let cubic = (1..10).map(|x| (x as f64, 0.5 * (x as f64).powi(3))).collect::<Vec<_>>();
+        assert!((fit_exponent(&cubic).unwrap() - 3.).abs() < 1e-9);
+
+        assert_eq!(fit_exponent(&[(1., 1.)]), None);
+        assert_eq!(fit_exponent(&[(2., 1.), (2., 5.)]), None);
+    }
+}
@@ -0,0 +1,251 @@
+//! Weighted shortest-path search over arbitrary state spaces
+//!
+//! States only need to be hashable and cloneable, so searches can track things like position,
+//! facing and move history together. Successors are produced by a closure returning each
+//! neighboring state along with the cost of moving to it.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+use fnv::FnvHashMap;
+
+use crate::grid::{Grid, GridPoint, Neighborhood};
+
+/// Result of a successful search
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult<S> {
+    /// Total cost of the path
+    pub cost: u64,
+
+    /// States visited along the path, from the start to the goal inclusive
+    pub path: Vec<S>,
+}
+
+/// Find the cheapest path from any start state to a goal state using Dijkstra's algorithm
+///
+/// `successors` returns the states reachable from a given state along with the cost of each
+/// move. Returns `None` if no goal is reachable.
+pub fn dijkstra<S, I, FN, FG>(
+    starts: impl IntoIterator<Item=S>,
+    successors: FN,
+    is_goal: FG,
+) -> Option<SearchResult<S>>
+where S: Clone + Eq + Hash,
+      I: IntoIterator<Item=(S, u64)>,
+      FN: FnMut(&S) -> I,
+      FG: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// Find the cheapest path from any start state to a goal state using A*
+///
+/// `heuristic` must never overestimate the remaining cost to the nearest goal, or the returned
+/// path may not be optimal. It need not be consistent: states reached more cheaply after being
+/// expanded are reopened. Returns `None` if no goal is reachable.
+pub fn astar<S, I, FN, FH, FG>(
+    starts: impl IntoIterator<Item=S>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<SearchResult<S>>
+where S: Clone + Eq + Hash,
+      I: IntoIterator<Item=(S, u64)>,
+      FN: FnMut(&S) -> I,
+      FH: FnMut(&S) -> u64,
+      FG: FnMut(&S) -> bool,
+{
+    struct Node<S> {
+        state: S,
+        cost: u64,
+        parent: Option<usize>,
+        closed: bool,
+    }
+
+    let mut nodes: Vec<Node<S>> = Vec::new();
+    let mut index = FnvHashMap::default();
+
+    // entries are (estimated total cost, cost so far, node index)
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if index.contains_key(&start) {
+            continue;
+        }
+        let est = (heuristic)(&start);
+        index.insert(start.clone(), nodes.len());
+        queue.push(Reverse((est, 0, nodes.len())));
+        nodes.push(Node { state: start, cost: 0, parent: None, closed: false });
+    }
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        if nodes[idx].closed || cost > nodes[idx].cost {
+            continue; // stale queue entry
+        }
+        nodes[idx].closed = true;
+
+        if (is_goal)(&nodes[idx].state) {
+            let mut path = Vec::new();
+            let mut cur = Some(idx);
+            while let Some(i) = cur {
+                path.push(nodes[i].state.clone());
+                cur = nodes[i].parent;
+            }
+            path.reverse();
+            return Some(SearchResult { cost, path });
+        }
+
+        for (next, step) in (successors)(&nodes[idx].state) {
+            let next_cost = cost + step;
+            match index.get(&next) {
+                Some(&n) => {
+                    if nodes[n].cost <= next_cost {
+                        continue;
+                    }
+                    nodes[n].cost = next_cost;
+                    nodes[n].closed = false;
+                    nodes[n].parent = Some(idx);
+                    queue.push(Reverse((next_cost + (heuristic)(&next), next_cost, n)));
+                }
+                None => {
+                    let n = nodes.len();
+                    queue.push(Reverse((next_cost + (heuristic)(&next), next_cost, n)));
+                    index.insert(next.clone(), n);
+                    nodes.push(Node {
+                        state: next,
+                        cost: next_cost,
+                        parent: Some(idx),
+                        closed: false,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// A move to a grid position along with its cost
+type GridStep = ((usize, usize), u64);
+
+/// Build a successor function for searching over positions on a grid
+///
+/// `cost(from, to)` returns the cost of a single step, or `None` if the move isn't allowed.
+pub fn grid_successors<'g, T, F>(
+    grid: &'g Grid<T>,
+    neighborhood: Neighborhood,
+    mut cost: F,
+) -> impl FnMut(&(usize, usize)) -> Vec<GridStep> + 'g
+where F: FnMut(GridPoint<'g, T>, GridPoint<'g, T>) -> Option<u64> + 'g,
+{
+    move |pos| {
+        let cur = grid.point(*pos);
+        neighborhood.directions().iter()
+            .filter_map(|dir| {
+                let next = cur.step(*dir)?;
+                Some((next.coords(), (cost)(cur, next)?))
+            })
+            .collect()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Find the cheapest path between two cells
+    ///
+    /// `cost(from, to)` returns the cost of a single step, or `None` if the move isn't allowed.
+    pub fn shortest_path<'g, F>(
+        &'g self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        neighborhood: Neighborhood,
+        cost: F,
+    ) -> Option<SearchResult<(usize, usize)>>
+    where F: FnMut(GridPoint<'g, T>, GridPoint<'g, T>) -> Option<u64> + 'g,
+    {
+        dijkstra([start], grid_successors(self, neighborhood, cost), |p| *p == goal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::{Direction, Pos};
+
+    fn weights() -> Grid<u64> {
+        Grid::from_data(vec![1, 9, 1, 1, 1,
+                             1, 9, 1, 9, 1,
+                             1, 1, 1, 9, 1], 5)
+    }
+
+    #[test]
+    fn grid_path() {
+        let grid = weights();
+        let result = grid.shortest_path((0, 0), (4, 2), Neighborhood::Four, |_, to| Some(*to))
+                    .unwrap();
+
+        assert_eq!(result.cost, 10);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&(4, 2)));
+        assert_eq!(result.path.len(), 11);
+
+        let walls = grid.shortest_path((0, 0), (4, 2), Neighborhood::Four,
+                                       |_, to| (*to < 9).then_some(*to));
+        assert_eq!(walls.map(|r| r.cost), Some(10));
+
+        let blocked = grid.shortest_path((0, 0), (4, 0), Neighborhood::Four,
+                                         |_, to| (*to < 5 && to.coords().0 < 2).then_some(1));
+        assert_eq!(blocked, None);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid = weights();
+        let goal = Pos::new(4, 2);
+        let heuristic = |p: &(usize, usize)| Pos::from(*p).manhattan(goal) as u64;
+
+        let a = astar([(0, 0)], grid_successors(&grid, Neighborhood::Four, |_, to| Some(*to)),
+                      heuristic, |p| *p == (4, 2)).unwrap();
+        let d = dijkstra([(0, 0)], grid_successors(&grid, Neighborhood::Four, |_, to| Some(*to)),
+                         |p| *p == (4, 2)).unwrap();
+        assert_eq!(a.cost, d.cost);
+    }
+
+    #[test]
+    fn inconsistent_heuristic() {
+        // h(A) = 5 is admissible but not consistent, so B is first closed via the worse route
+        let edges = |s: &char| match s {
+            'S' => vec![('A', 1), ('B', 4)],
+            'A' => vec![('B', 1)],
+            'B' => vec![('G', 4)],
+            _   => vec![],
+        };
+        let heuristic = |s: &char| if *s == 'A' { 5 } else { 0 };
+
+        let result = astar(['S'], edges, heuristic, |s| *s == 'G').unwrap();
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path, vec!['S', 'A', 'B', 'G']);
+    }
+
+    #[test]
+    fn rich_state() {
+        // never move twice in a row in the same direction, and never reverse
+        let grid = Grid::filled(3, 3, 1u64);
+        let start = ((0, 0), Direction::Right);
+
+        let result = dijkstra(
+            [start],
+            |&(pos, dir): &((usize, usize), Direction)| {
+                let cur = grid.point(pos);
+                [dir.turn_left(), dir.turn_right()].into_iter()
+                    .filter_map(|d| Some(((cur.step(d)?.coords(), d), *cur.step(d)?)))
+                    .collect::<Vec<_>>()
+            },
+            |(pos, _)| *pos == (2, 0),
+        ).unwrap();
+
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.path.len(), 5);
+        assert!(result.path.windows(2).all(|w| w[0].1 != w[1].1));
+    }
+}
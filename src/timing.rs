@@ -0,0 +1,155 @@
+//! Scoped phase timers for use inside solvers
+//!
+//! Solvers mark interesting phases with [`span`], which returns a guard that records the elapsed
+//! time when dropped. Spans opened while another span is live on the same thread are nested under
+//! it. Timing is off by default, in which case creating a span costs a single atomic load.
+//!
+//! ```ignore
+//! let _t = crate::timing::span("seed_soil");
+//! ```
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Vec<SpanStats>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Turn span collection on or off
+pub fn enable(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// Check whether span collection is currently on
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start timing a named phase, which ends when the returned guard is dropped
+#[inline]
+pub fn span(name: &'static str) -> Span {
+    if !enabled() {
+        return Span { start: None };
+    }
+
+    STACK.with(|s| s.borrow_mut().push(name));
+    Span { start: Some(Instant::now()) }
+}
+
+/// Take all spans recorded since the last call
+pub fn take() -> Report {
+    let spans = std::mem::take(&mut *SPANS.lock().expect("Timing data poisoned"));
+    Report { spans }
+}
+
+/// Guard for a running timed phase
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct Span {
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.start else { return; };
+        let elapsed = start.elapsed();
+
+        let path = STACK.with(|s| {
+            let mut s = s.borrow_mut();
+            let path = s.clone();
+            s.pop();
+            path
+        });
+
+        let mut spans = SPANS.lock().expect("Timing data poisoned");
+        match spans.iter_mut().find(|x| x.path == path) {
+            Some(stats) => {
+                stats.total += elapsed;
+                stats.count += 1;
+            }
+            None => {
+                spans.push(SpanStats { path, total: elapsed, count: 1 });
+            }
+        }
+    }
+}
+
+/// Accumulated timing of a single named phase
+#[derive(Clone, Debug)]
+pub struct SpanStats {
+    /// Names of the enclosing spans, ending with this one
+    pub path: Vec<&'static str>,
+
+    /// Total time spent in this span
+    pub total: Duration,
+
+    /// Number of times the span was entered
+    pub count: usize,
+}
+
+/// Aggregated set of recorded spans
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    spans: Vec<SpanStats>,
+}
+
+impl Report {
+    /// Check whether any spans were recorded
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    fn fmt_level(
+        &self,
+        f: &mut std::fmt::Formatter,
+        parent: &[&'static str],
+    ) -> std::fmt::Result {
+        let children = self.spans.iter()
+                      .filter(|s| s.path.len() == parent.len() + 1 && s.path.starts_with(parent));
+        for child in children {
+            let name = child.path.last().unwrap();
+            let indent = 2 * parent.len();
+            writeln!(f, "    {:indent$}{:<width$} {:<12?} ({} calls)",
+                     "", name, child.total, child.count,
+                     width = 20usize.saturating_sub(indent))?;
+            self.fmt_level(f, &child.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.fmt_level(f, &[])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nested_spans() {
+        enable(true);
+        {
+            let _outer = span("outer");
+            for _ in 0..3 {
+                let _inner = span("inner");
+            }
+        }
+        enable(false);
+        {
+            let _ignored = span("ignored");
+        }
+
+        let report = take();
+        let paths = report.spans.iter().map(|s| (s.path.clone(), s.count)).collect::<Vec<_>>();
+        assert_eq!(paths, vec![(vec!["outer", "inner"], 3), (vec!["outer"], 1)]);
+        assert!(report.to_string().contains("  inner"));
+    }
+}
@@ -49,37 +49,75 @@ where anyhow::Error: From<T::Err>,
 pub fn load_grid<T: TryFrom<char>>(
     input: &mut dyn std::io::BufRead
 ) -> Result<crate::grid::Grid<T>>
-where T::Error: Send+Sync,
-      anyhow::Error: From<T::Error>,
+where anyhow::Error: From<T::Error>,
 {
-    use crate::grid::Grid;
+    load_grid_with(input, |c| Ok(T::try_from(c)?))
+}
 
-    let mut data = Vec::new();
-    let mut width = None;
+/// Load a grid, converting each character with a fallible closure
+///
+/// Conversion errors are reported with the line and column of the offending character.
+pub fn load_grid_with<T, F: FnMut(char) -> Result<T>>(
+    input: &mut dyn std::io::BufRead,
+    func: F,
+) -> Result<crate::grid::Grid<T>> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    crate::grid::Grid::parse_with(&text, func)
+}
 
-    let mut line = String::new();
-    while input.read_line(&mut line)? > 0 {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+/// Load a grid of raw bytes, without any per-character conversion
+pub fn load_byte_grid(
+    input: &mut dyn std::io::BufRead
+) -> Result<crate::grid::Grid<u8>> {
+    let mut text = Vec::new();
+    input.read_to_end(&mut text)?;
+    crate::grid::Grid::from_bytes(&text)
+}
 
-        match width.as_ref() {
-            Some(w) => {
-                anyhow::ensure!(*w == trimmed.len(), "Grid rows are not allowed to vary in width");
-            }
-            None => {
-                width = Some(trimmed.len());
-            }
-        }
+/// Small deterministic pseudo-random number generator for building synthetic inputs
+///
+/// This is xorshift64*, which is plenty for generating test data and avoids pulling in a
+/// dependency.
+pub struct Rng(u64);
 
-        // parse this line
-        for c in trimmed.chars() {
-            data.push(T::try_from(c)?);
-        }
+impl Rng {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        // the all-zero state is a fixed point, so avoid it
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
 
-        line.clear();
+    /// Get the next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
     }
 
-    Ok(Grid::from_data(data, width.unwrap_or(0)))
+    /// Get a value uniformly distributed in `0..n`
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Empty range");
+
+        // reject the lowest 2^64 % n values, so the rest split evenly into n buckets
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let x = self.next_u64();
+            if x >= threshold {
+                return x % n;
+            }
+        }
+    }
+
+    /// Pick a random element of a slice
+    ///
+    /// # Panics
+    /// Panics if the slice is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
 }